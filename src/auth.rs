@@ -1,11 +1,17 @@
-use std::time::Duration;
+use std::{fmt::Display, ops::Deref, sync::RwLockReadGuard, time::Duration};
 
 use chrono::{DateTime, Utc};
 use oauth2::{
-    basic::BasicTokenType, AccessToken, ClientId, ClientSecret, CsrfToken, PkceCodeVerifier,
-    RefreshToken, Scope, TokenResponse,
+    basic::{BasicTokenResponse, BasicTokenType},
+    AccessToken, AuthUrl, ClientId, ClientSecret, CsrfToken, PkceCodeVerifier, RefreshToken,
+    Scope as OAuthScope, TokenResponse, TokenUrl,
 };
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+const AUTHORISATION_URL: &str = "https://accounts.spotify.com/authorize";
+const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
 
 pub trait AuthenticationState: private::Sealed {}
 impl AuthenticationState for Token {}
@@ -15,6 +21,8 @@ pub trait AuthFlow: private::Sealed {
     fn client_id(&self) -> ClientId;
     fn client_secret(&self) -> Option<ClientSecret>;
     fn scopes(self) -> Option<Vec<oauth2::Scope>>;
+    fn auth_url(&self) -> AuthUrl;
+    fn token_url(&self) -> TokenUrl;
 }
 
 pub trait Refreshable: private::Sealed {}
@@ -49,12 +57,15 @@ mod private {
 }
 
 /// A Spotify token.
+///
+/// With the `zeroize` feature enabled, the access and refresh tokens are wiped from memory
+/// when the token is dropped, which includes when it's replaced after being refreshed.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Token {
     /// The token used for authenticating every single request.
-    pub access_token: AccessToken,
+    pub access_token: Secret,
     /// The token used for requesting a new access token when the current one expires.
-    pub refresh_token: Option<RefreshToken>,
+    pub refresh_token: Option<Secret>,
     /// How long until the current token expires, in seconds.
     pub expires_in: u64,
 
@@ -75,6 +86,169 @@ pub struct Token {
     pub(crate) scopes: Option<Vec<oauth2::Scope>>,
 }
 
+/// A secret string, such as the access or refresh token, which isn't shown when debug-formatted.
+///
+/// With the `zeroize` feature enabled, it's wiped from memory when dropped. It can be converted into
+/// [`oauth2`]'s [`AccessToken`] and [`RefreshToken`], but those copies aren't wiped.
+#[derive(Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    /// Get the secret value.
+    pub fn secret(&self) -> &str {
+        &self.0
+    }
+
+    /// Overwrite the secret with zeroes, leaving it empty.
+    #[cfg(feature = "zeroize")]
+    pub(crate) fn wipe(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Secret([redacted])")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Secret {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+/// A secret of the client's current token, as returned by
+/// [`Client::access_token`](crate::client::Client::access_token) and
+/// [`Client::refresh_token`](crate::client::Client::refresh_token), which dereferences to a `str`.
+///
+/// It borrows the token (shared with all clones of the client) instead of copying the secret,
+/// so the token can't be refreshed while it's held. Don't hold it across an `.await`;
+/// use `to_owned` if the secret is needed for longer.
+pub struct SecretRef<'a> {
+    token: RwLockReadGuard<'a, Token>,
+    secret: fn(&Token) -> &str,
+}
+
+impl<'a> SecretRef<'a> {
+    pub(crate) fn new(token: RwLockReadGuard<'a, Token>, secret: fn(&Token) -> &str) -> Self {
+        Self { token, secret }
+    }
+}
+
+impl Deref for SecretRef<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        (self.secret)(&self.token)
+    }
+}
+
+impl std::fmt::Debug for SecretRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretRef([redacted])")
+    }
+}
+
+impl From<&Secret> for AccessToken {
+    fn from(value: &Secret) -> Self {
+        AccessToken::new(value.0.clone())
+    }
+}
+
+impl From<&Secret> for RefreshToken {
+    fn from(value: &Secret) -> Self {
+        RefreshToken::new(value.0.clone())
+    }
+}
+
+/// A [scope](https://developer.spotify.com/documentation/web-api/concepts/scopes) documented by Spotify.
+///
+/// The auth flows accept these as well as plain strings, so scopes that aren't (yet)
+/// listed here can still be used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Scope {
+    UgcImageUpload,
+    UserReadPlaybackState,
+    UserModifyPlaybackState,
+    UserReadCurrentlyPlaying,
+    AppRemoteControl,
+    Streaming,
+    PlaylistReadPrivate,
+    PlaylistReadCollaborative,
+    PlaylistModifyPrivate,
+    PlaylistModifyPublic,
+    UserFollowModify,
+    UserFollowRead,
+    UserReadPlaybackPosition,
+    UserTopRead,
+    UserReadRecentlyPlayed,
+    UserLibraryModify,
+    UserLibraryRead,
+    UserReadEmail,
+    UserReadPrivate,
+    UserSoaLink,
+    UserSoaUnlink,
+    SoaManageEntitlements,
+    SoaManagePartner,
+    SoaCreatePartner,
+}
+
+impl AsRef<str> for Scope {
+    fn as_ref(&self) -> &str {
+        match self {
+            Scope::UgcImageUpload => "ugc-image-upload",
+            Scope::UserReadPlaybackState => "user-read-playback-state",
+            Scope::UserModifyPlaybackState => "user-modify-playback-state",
+            Scope::UserReadCurrentlyPlaying => "user-read-currently-playing",
+            Scope::AppRemoteControl => "app-remote-control",
+            Scope::Streaming => "streaming",
+            Scope::PlaylistReadPrivate => "playlist-read-private",
+            Scope::PlaylistReadCollaborative => "playlist-read-collaborative",
+            Scope::PlaylistModifyPrivate => "playlist-modify-private",
+            Scope::PlaylistModifyPublic => "playlist-modify-public",
+            Scope::UserFollowModify => "user-follow-modify",
+            Scope::UserFollowRead => "user-follow-read",
+            Scope::UserReadPlaybackPosition => "user-read-playback-position",
+            Scope::UserTopRead => "user-top-read",
+            Scope::UserReadRecentlyPlayed => "user-read-recently-played",
+            Scope::UserLibraryModify => "user-library-modify",
+            Scope::UserLibraryRead => "user-library-read",
+            Scope::UserReadEmail => "user-read-email",
+            Scope::UserReadPrivate => "user-read-private",
+            Scope::UserSoaLink => "user-soa-link",
+            Scope::UserSoaUnlink => "user-soa-unlink",
+            Scope::SoaManageEntitlements => "soa-manage-entitlements",
+            Scope::SoaManagePartner => "soa-manage-partner",
+            Scope::SoaCreatePartner => "soa-create-partner",
+        }
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl From<Scope> for String {
+    fn from(value: Scope) -> Self {
+        value.as_ref().to_owned()
+    }
+}
+
+impl From<&Scope> for String {
+    fn from(value: &Scope) -> Self {
+        value.as_ref().to_owned()
+    }
+}
+
 #[derive(Clone, Copy, Debug)]
 pub struct UnAuthenticated;
 
@@ -82,19 +256,29 @@ pub struct UnAuthenticated;
 pub struct AuthCodeFlow {
     pub client_id: String,
     pub client_secret: String,
-    pub scopes: Vec<Scope>,
+    pub scopes: Vec<OAuthScope>,
+    /// The URL the user is sent to in order to authorise the app. Defaults to Spotify's.
+    pub auth_url: AuthUrl,
+    /// The URL used for requesting and refreshing tokens. Defaults to Spotify's.
+    pub token_url: TokenUrl,
 }
 
 #[derive(Clone, Debug)]
 pub struct AuthCodePkceFlow {
     pub client_id: String,
-    pub scopes: Vec<Scope>,
+    pub scopes: Vec<OAuthScope>,
+    /// The URL the user is sent to in order to authorise the app. Defaults to Spotify's.
+    pub auth_url: AuthUrl,
+    /// The URL used for requesting and refreshing tokens. Defaults to Spotify's.
+    pub token_url: TokenUrl,
 }
 
 #[derive(Clone, Debug)]
 pub struct ClientCredsFlow {
     pub client_id: String,
     pub client_secret: String,
+    /// The URL used for requesting tokens. Defaults to Spotify's.
+    pub token_url: TokenUrl,
 }
 
 #[derive(Debug)]
@@ -121,6 +305,14 @@ impl AuthFlow for AuthCodeFlow {
     fn scopes(self) -> Option<Vec<oauth2::Scope>> {
         Some(self.scopes)
     }
+
+    fn auth_url(&self) -> AuthUrl {
+        self.auth_url.clone()
+    }
+
+    fn token_url(&self) -> TokenUrl {
+        self.token_url.clone()
+    }
 }
 
 impl AuthFlow for AuthCodePkceFlow {
@@ -135,6 +327,14 @@ impl AuthFlow for AuthCodePkceFlow {
     fn scopes(self) -> Option<Vec<oauth2::Scope>> {
         Some(self.scopes)
     }
+
+    fn auth_url(&self) -> AuthUrl {
+        self.auth_url.clone()
+    }
+
+    fn token_url(&self) -> TokenUrl {
+        self.token_url.clone()
+    }
 }
 
 impl AuthFlow for ClientCredsFlow {
@@ -149,9 +349,35 @@ impl AuthFlow for ClientCredsFlow {
     fn scopes(self) -> Option<Vec<oauth2::Scope>> {
         None
     }
+
+    fn auth_url(&self) -> AuthUrl {
+        default_auth_url()
+    }
+
+    fn token_url(&self) -> TokenUrl {
+        self.token_url.clone()
+    }
 }
 
 impl Token {
+    /// Create a token from one obtained elsewhere (e.g. loaded by a custom [`TokenStore`](crate::store::TokenStore)),
+    /// which expires `expires_in` seconds from now.
+    ///
+    /// `created_at` and `expires_at` can be changed afterwards if the token was created earlier.
+    pub fn new(
+        access_token: impl Into<String>,
+        refresh_token: Option<String>,
+        expires_in: u64,
+    ) -> Self {
+        Self {
+            access_token: Secret::new(access_token),
+            refresh_token: refresh_token.map(Secret::new),
+            expires_in,
+            ..Self::logged_out()
+        }
+        .set_timestamps()
+    }
+
     pub(crate) fn set_timestamps(self) -> Self {
         let created_at = Utc::now();
 
@@ -166,36 +392,51 @@ impl Token {
         }
     }
 
+    /// An expired token without a refresh token, which replaces the actual token when logging out.
+    pub(crate) fn logged_out() -> Self {
+        let now = Utc::now();
+
+        Self {
+            access_token: Secret::new(String::new()),
+            refresh_token: None,
+            expires_in: 0,
+            created_at: now,
+            expires_at: now,
+            token_type: BasicTokenType::Bearer,
+            scopes: None,
+        }
+    }
+
     /// Returns `true` if the access token has expired.
     pub fn is_expired(&self) -> bool {
         Utc::now() >= self.expires_at
     }
 
+    /// The amount of time until the access token expires, which is zero if it has already expired.
+    pub fn time_until_expiry(&self) -> Duration {
+        (self.expires_at - Utc::now()).to_std().unwrap_or_default()
+    }
+
     /// Returns `true` if a refresh token is present.
     pub fn is_refreshable(&self) -> bool {
         self.refresh_token.is_some()
     }
 }
 
-impl TokenResponse<BasicTokenType> for Token {
-    fn access_token(&self) -> &AccessToken {
-        &self.access_token
-    }
-
-    fn token_type(&self) -> &BasicTokenType {
-        &self.token_type
-    }
-
-    fn expires_in(&self) -> Option<Duration> {
-        Some(Duration::from_secs(self.expires_in))
-    }
-
-    fn refresh_token(&self) -> Option<&RefreshToken> {
-        self.refresh_token.as_ref()
-    }
-
-    fn scopes(&self) -> Option<&Vec<oauth2::Scope>> {
-        self.scopes.as_ref()
+impl From<BasicTokenResponse> for Token {
+    fn from(value: BasicTokenResponse) -> Self {
+        Self {
+            access_token: Secret::new(value.access_token().secret().as_str()),
+            refresh_token: value
+                .refresh_token()
+                .map(|t| Secret::new(t.secret().as_str())),
+            // Spotify always includes the lifetime of its tokens, which is one hour.
+            expires_in: value.expires_in().map_or(3600, |d| d.as_secs()),
+            token_type: value.token_type().clone(),
+            scopes: value.scopes().cloned(),
+            ..Self::logged_out()
+        }
+        .set_timestamps()
     }
 }
 
@@ -208,7 +449,12 @@ impl AuthCodeFlow {
         Self {
             client_id: client_id.into(),
             client_secret: client_secret.into(),
-            scopes: scopes.into_iter().map(|s| Scope::new(s.into())).collect(),
+            scopes: scopes
+                .into_iter()
+                .map(|s| OAuthScope::new(s.into()))
+                .collect(),
+            auth_url: default_auth_url(),
+            token_url: default_token_url(),
         }
     }
 }
@@ -221,7 +467,12 @@ impl AuthCodePkceFlow {
     {
         Self {
             client_id: client_id.into(),
-            scopes: scopes.into_iter().map(|s| Scope::new(s.into())).collect(),
+            scopes: scopes
+                .into_iter()
+                .map(|s| OAuthScope::new(s.into()))
+                .collect(),
+            auth_url: default_auth_url(),
+            token_url: default_token_url(),
         }
     }
 }
@@ -231,6 +482,55 @@ impl ClientCredsFlow {
         Self {
             client_id: client_id.into(),
             client_secret: client_secret.into(),
+            token_url: default_token_url(),
         }
     }
 }
+
+fn default_auth_url() -> AuthUrl {
+    AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap()
+}
+
+fn default_token_url() -> TokenUrl {
+    TokenUrl::new(TOKEN_URL.to_owned()).unwrap()
+}
+
+/// Get the auth code and (CSRF) state from the URL the user was redirected to after authorising
+/// the app, which can be passed straight to `authenticate`.
+///
+/// If the user denied access (or authorisation failed for another reason),
+/// [`CallbackError::AuthorizationDenied`] is returned instead, with the reason Spotify gave.
+pub fn parse_callback(redirect_url: &Url) -> Result<(String, String), CallbackError> {
+    let param = |name: &str| {
+        redirect_url
+            .query_pairs()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.into_owned())
+    };
+
+    if let Some(error) = param("error") {
+        return Err(CallbackError::AuthorizationDenied {
+            reason: param("error_description").unwrap_or(error),
+        });
+    }
+
+    let code = param("code").ok_or(CallbackError::MissingParameter("code"))?;
+    let state = param("state").ok_or(CallbackError::MissingParameter("state"))?;
+
+    Ok((code, state))
+}
+
+/// The error returned when the redirect URL doesn't contain an auth code and state.
+///
+/// It converts into [`Error`](crate::Error), with a denial becoming
+/// [`Error::AuthorizationDenied`](crate::Error::AuthorizationDenied).
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum CallbackError {
+    /// The user denied access to the app (the reason is usually `access_denied`),
+    /// or authorisation failed for another reason.
+    #[error("Authorisation was denied: {reason}")]
+    AuthorizationDenied { reason: String },
+    /// The URL doesn't contain the given query parameter.
+    #[error("The redirect URL is missing the \"{0}\" parameter.")]
+    MissingParameter(&'static str),
+}
@@ -1,23 +1,34 @@
-use std::marker::PhantomData;
+use std::{
+    marker::PhantomData,
+    sync::{Arc, OnceLock, PoisonError, RwLock, RwLockReadGuard},
+    time::Duration,
+};
 
 use base64::{engine::general_purpose, Engine};
+use futures_util::{stream, Stream};
 use oauth2::{
     basic::{
         BasicErrorResponse, BasicRevocationErrorResponse, BasicTokenIntrospectionResponse,
-        BasicTokenType,
+        BasicTokenResponse, BasicTokenType,
     },
     reqwest::async_http_client,
-    AuthUrl, AuthorizationCode, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge, RedirectUrl,
-    RefreshToken, StandardRevocableToken, TokenUrl,
+    AuthorizationCode, ClientId, ClientSecret, CsrfToken, PkceCodeChallenge, RedirectUrl,
+    RefreshToken, StandardRevocableToken,
+};
+use reqwest::{
+    header::{HeaderMap, CONTENT_LENGTH},
+    Method, RequestBuilder, StatusCode, Url,
 };
-use reqwest::{header::CONTENT_LENGTH, Method, Url};
 use serde::{de::DeserializeOwned, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
+use tokio::sync::{Mutex, Semaphore};
+use tracing::Instrument;
 
 use crate::{
     auth::{
         AuthCodeFlow, AuthCodePkceFlow, AuthFlow, AuthenticationState, Authorised, ClientCredsFlow,
-        CsrfVerifier, NoVerifier, PkceVerifier, Token, UnAuthenticated, Verifier,
+        CsrfVerifier, NoVerifier, PkceVerifier, Secret, SecretRef, Token, UnAuthenticated,
+        Verifier,
     },
     body_list,
     endpoint::{
@@ -33,26 +44,43 @@ use crate::{
         user::*,
         Builder, Endpoint,
     },
-    error::{Error, Result, SpotifyError},
+    error::{Error, Result},
     model::{
         artist::{Artist, Artists},
-        audio::{AudioAnalysis, AudioFeatures, AudioFeaturesResult},
         market::Markets,
         player::{Device, Devices, PlaybackState, Queue},
+        playlist::SnapshotId,
         recommendation::Genres,
         search::Item,
-        user::{User, UserItemType},
+        uri::ContextUri,
+        user::{PrivateUser, Product, User, UserItemType},
         Image,
     },
-    query_list, Nil,
+    query_list,
+    store::TokenStore,
+    transport::HttpTransport,
+    Nil,
 };
 
-const AUTHORISATION_URL: &str = "https://accounts.spotify.com/authorize";
-const TOKEN_URL: &str = "https://accounts.spotify.com/api/token";
+#[cfg(feature = "deprecated-endpoints")]
+use crate::model::audio::{AudioAnalysis, AudioFeatures, AudioFeaturesResult};
+
+const API_URL: &str = "https://api.spotify.com/v1";
+/// The response header containing the ID Spotify assigned to the request.
+const REQUEST_ID: &str = "x-request-id";
+const DEFAULT_REFRESH_SKEW: Duration = Duration::from_secs(60);
+/// The maximum size of a (Base64 encoded) playlist cover image.
+const MAX_IMAGE_SIZE: usize = 256 * 1024;
+/// The item types requested from playlists by default, so episodes aren't returned as tracks.
+const DEFAULT_ADDITIONAL_TYPES: &str = "track,episode";
+/// The maximum amount of IDs that can be saved to (or removed from) the user's library at once.
+const MAX_LIBRARY_IDS: usize = 50;
+/// Same as [`MAX_LIBRARY_IDS`], but for albums, which have a lower limit.
+const MAX_LIBRARY_ALBUM_IDS: usize = 20;
 
 pub(crate) type OAuthClient = oauth2::Client<
     BasicErrorResponse,
-    Token,
+    BasicTokenResponse,
     BasicTokenType,
     BasicTokenIntrospectionResponse,
     StandardRevocableToken,
@@ -78,6 +106,35 @@ pub(crate) enum Body<P: Serialize = ()> {
 ///
 /// It is recommended to use one of the following: [`AuthCodeClient`], [`AuthCodePkceClient`] or [`ClientCredsClient`],
 /// depending on the chosen auth flow.
+///
+/// An authenticated client can be cloned cheaply. Clones share the same token (so a refresh done
+/// by one of them is visible to all of them), HTTP client, transport and concurrency limit, while
+/// settings such as [`auto_refresh`](Self::auto_refresh) can be changed for each clone separately.
+///
+/// Requests only need a shared reference to the client, so several of them can be sent
+/// concurrently, e.g. with `tokio::join!`:
+///
+/// ```no_run
+/// # use spotify_rs::{auth::{NoVerifier, Token}, client::Client, model::user::UserItemType, AuthCodeFlow};
+/// # async fn f(spotify: Client<Token, AuthCodeFlow, NoVerifier>) -> spotify_rs::SpotifyResult<()> {
+/// let (profile, top_tracks, saved_albums) = tokio::join!(
+///     spotify.get_current_user_profile(),
+///     spotify.current_user_top_items(UserItemType::Tracks).get(),
+///     spotify.saved_albums().get(),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The client can also be shared between tasks (e.g. behind an [`Arc`]). If the token needs
+/// to be refreshed while several requests are in flight, it's only refreshed once.
+///
+/// # Library IDs
+///
+/// The methods that save, remove or check items in the user's library (e.g. [`save_tracks`](Self::save_tracks))
+/// and check whether the user follows artists or users accept any number of IDs. They're sent in chunks
+/// of the most Spotify allows at once (20 for albums, 50 otherwise), one request at a time.
+/// If a request fails, its error is returned and the chunks sent before it stay applied.
 #[derive(Debug)]
 pub struct Client<A: AuthenticationState, F: AuthFlow, V: Verifier> {
     /// Dictates whether or not the client will request a new token when the
@@ -85,13 +142,55 @@ pub struct Client<A: AuthenticationState, F: AuthFlow, V: Verifier> {
     ///
     /// It will check if the token has expired in every request.
     pub auto_refresh: bool,
-    pub(crate) auth: A,
+    /// The base URL all API requests are sent to. Defaults to `https://api.spotify.com/v1`.
+    ///
+    /// Useful for testing against a mock server.
+    pub api_url: String,
+    pub(crate) auth: Arc<RwLock<A>>,
     pub(crate) oauth: OAuthClient,
     pub(crate) http: reqwest::Client,
+    pub(crate) transport: Arc<dyn HttpTransport>,
+    pub(crate) limiter: Option<Arc<Semaphore>>,
+    pub(crate) refresh_lock: Arc<Mutex<()>>,
+    pub(crate) refresh_skew: Duration,
+    pub(crate) user_id: OnceLock<String>,
+    pub(crate) premium: OnceLock<bool>,
+    pub(crate) genre_seeds: OnceLock<Vec<String>>,
+    pub(crate) token_store: Option<Arc<dyn TokenStore>>,
     pub(crate) verifier: V,
     marker: PhantomData<F>,
 }
 
+impl<A: AuthenticationState, F: AuthFlow, V: Verifier> Client<A, F, V> {
+    /// Limit the amount of requests the client can have in flight at the same time.
+    ///
+    /// Requests over the limit will wait until one of the in-flight requests completes.
+    /// Passing `None` removes the limit, which is the default. A limit of `0` is treated as `1`.
+    pub fn set_max_concurrent_requests(&mut self, max_concurrent_requests: Option<usize>) {
+        self.limiter = max_concurrent_requests.map(|n| Arc::new(Semaphore::new(n.max(1))));
+    }
+
+    /// Set the HTTP transport used to send the API requests. See [`HttpTransport`] for more details.
+    pub fn set_transport(&mut self, transport: impl HttpTransport + 'static) {
+        self.transport = Arc::new(transport);
+    }
+
+    /// Set the store the token is saved to every time it's refreshed. See [`TokenStore`] for more details.
+    ///
+    /// The current token isn't saved when setting the store.
+    pub fn set_token_store(&mut self, token_store: impl TokenStore + 'static) {
+        self.token_store = Some(Arc::new(token_store));
+    }
+
+    /// Set how long before its actual expiry the access token is considered expired
+    /// (and refreshed, if possible). The default is 60 seconds.
+    ///
+    /// This prevents the token from expiring between checking it and the request reaching Spotify.
+    pub fn set_refresh_skew(&mut self, refresh_skew: Duration) {
+        self.refresh_skew = refresh_skew;
+    }
+}
+
 impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
     /// Create a new client and generate an authorisation URL
     ///
@@ -104,6 +203,8 @@ impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
             client_id,
             client_secret,
             scopes,
+            auth_url,
+            token_url,
         }: AuthCodeFlow,
         redirect_uri: RedirectUrl,
         auto_refresh: bool,
@@ -111,8 +212,8 @@ impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
         let oauth = OAuthClient::new(
             ClientId::new(client_id),
             Some(ClientSecret::new(client_secret)),
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
+            auth_url,
+            Some(token_url),
         )
         .set_redirect_uri(redirect_uri);
 
@@ -124,9 +225,18 @@ impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
         (
             Client {
                 auto_refresh,
-                auth: UnAuthenticated,
+                api_url: API_URL.to_owned(),
+                auth: Arc::new(RwLock::new(UnAuthenticated)),
                 oauth,
                 http: reqwest::Client::new(),
+                transport: Arc::new(reqwest::Client::new()),
+                limiter: None,
+                refresh_lock: Arc::default(),
+                refresh_skew: DEFAULT_REFRESH_SKEW,
+                user_id: OnceLock::new(),
+                premium: OnceLock::new(),
+                genre_seeds: OnceLock::new(),
+                token_store: None,
                 verifier: CsrfVerifier(csrf_token),
                 marker: PhantomData,
             },
@@ -143,17 +253,17 @@ impl Client<UnAuthenticated, AuthCodePkceFlow, PkceVerifier> {
     ///
     /// They are required for the next step in the auth process.
     pub fn new(
-        AuthCodePkceFlow { client_id, scopes }: AuthCodePkceFlow,
+        AuthCodePkceFlow {
+            client_id,
+            scopes,
+            auth_url,
+            token_url,
+        }: AuthCodePkceFlow,
         redirect_uri: RedirectUrl,
         auto_refresh: bool,
     ) -> (Self, Url) {
-        let oauth = OAuthClient::new(
-            ClientId::new(client_id),
-            None,
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
-        )
-        .set_redirect_uri(redirect_uri);
+        let oauth = OAuthClient::new(ClientId::new(client_id), None, auth_url, Some(token_url))
+            .set_redirect_uri(redirect_uri);
 
         let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
 
@@ -166,9 +276,18 @@ impl Client<UnAuthenticated, AuthCodePkceFlow, PkceVerifier> {
         (
             Client {
                 auto_refresh,
-                auth: UnAuthenticated,
+                api_url: API_URL.to_owned(),
+                auth: Arc::new(RwLock::new(UnAuthenticated)),
                 oauth,
                 http: reqwest::Client::new(),
+                transport: Arc::new(reqwest::Client::new()),
+                limiter: None,
+                refresh_lock: Arc::default(),
+                refresh_skew: DEFAULT_REFRESH_SKEW,
+                user_id: OnceLock::new(),
+                premium: OnceLock::new(),
+                genre_seeds: OnceLock::new(),
+                token_store: None,
                 verifier: PkceVerifier {
                     csrf_token,
                     pkce_verifier,
@@ -193,8 +312,8 @@ impl<F: AuthFlow> Client<Token, F, NoVerifier> {
         let oauth_client = OAuthClient::new(
             auth_flow.client_id(),
             auth_flow.client_secret(),
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
+            auth_flow.auth_url(),
+            Some(auth_flow.token_url()),
         );
 
         let refresh_token = RefreshToken::new(refresh_token);
@@ -205,71 +324,295 @@ impl<F: AuthFlow> Client<Token, F, NoVerifier> {
             req = req.add_scopes(scopes);
         }
 
-        let token = req.request_async(async_http_client).await?.set_timestamps();
+        let token: Token = req.request_async(async_http_client).await?.into();
 
         Ok(Client {
             auto_refresh,
-            auth: token,
+            api_url: API_URL.to_owned(),
+            auth: Arc::new(RwLock::new(token)),
             oauth: oauth_client,
             http: reqwest::Client::new(),
+            transport: Arc::new(reqwest::Client::new()),
+            limiter: None,
+            refresh_lock: Arc::default(),
+            refresh_skew: DEFAULT_REFRESH_SKEW,
+            user_id: OnceLock::new(),
+            premium: OnceLock::new(),
+            genre_seeds: OnceLock::new(),
+            token_store: None,
             verifier: NoVerifier,
             marker: PhantomData,
         })
     }
 }
 
+impl<F: AuthFlow> Clone for Client<Token, F, NoVerifier> {
+    fn clone(&self) -> Self {
+        Self {
+            auto_refresh: self.auto_refresh,
+            api_url: self.api_url.clone(),
+            auth: self.auth.clone(),
+            oauth: self.oauth.clone(),
+            http: self.http.clone(),
+            transport: self.transport.clone(),
+            limiter: self.limiter.clone(),
+            refresh_lock: self.refresh_lock.clone(),
+            refresh_skew: self.refresh_skew,
+            user_id: self.user_id.clone(),
+            premium: self.premium.clone(),
+            genre_seeds: self.genre_seeds.clone(),
+            token_store: self.token_store.clone(),
+            verifier: NoVerifier,
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     /// Get the current access token.
-    pub fn access_token(&self) -> &str {
-        self.auth.access_token.secret()
+    ///
+    /// It borrows the token shared with the client's clones, so it shouldn't be held
+    /// across an `.await`. See [`SecretRef`] for more details.
+    pub fn access_token(&self) -> SecretRef<'_> {
+        SecretRef::new(self.token(), |token| token.access_token.secret())
     }
 
     /// Get the current refresh token. Some auth flows may not provide a refresh token,
     /// in which case it's `None`.
-    pub fn refresh_token(&self) -> Option<&str> {
-        self.auth
-            .refresh_token
-            .as_ref()
-            .map(|t| t.secret().as_str())
+    ///
+    /// It borrows the token shared with the client's clones, so it shouldn't be held
+    /// across an `.await`. See [`SecretRef`] for more details.
+    pub fn refresh_token(&self) -> Option<SecretRef<'_>> {
+        let token = self.token();
+        token.refresh_token.as_ref()?;
+
+        Some(SecretRef::new(token, |token| {
+            token.refresh_token.as_ref().map_or("", Secret::secret)
+        }))
+    }
+
+    /// The current token, which is shared with all clones of this client.
+    pub(crate) fn token(&self) -> RwLockReadGuard<'_, Token> {
+        // The token is always replaced as a whole, so it can't be left in an invalid state.
+        self.auth.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Replace the token, for this client and all of its clones.
+    ///
+    /// With the `zeroize` feature, the secrets of the previous token are wiped as it's dropped.
+    pub(crate) fn set_token(&self, token: Token) {
+        let previous = std::mem::replace(
+            &mut *self.auth.write().unwrap_or_else(PoisonError::into_inner),
+            token,
+        );
+
+        drop(previous);
     }
 
     /// Request a new refresh token and updates it in the client.
     /// Only some auth flows allow for token refreshing.
-    pub async fn request_refresh_token(&mut self) -> Result<()> {
-        let Some(refresh_token) = &self.auth.refresh_token else {
+    pub async fn request_refresh_token(&self) -> Result<()> {
+        let _refreshing = self.refresh_lock.lock().await;
+        self.refresh().await
+    }
+
+    /// Refresh the token, unless it has been replaced since `seen` (its access token) was read,
+    /// returning whether it was refreshed.
+    ///
+    /// When requests are sent concurrently, only the first one refreshes the token, while the
+    /// others wait for it and then use the new token. This matters as Spotify can rotate the
+    /// refresh token, so a second refresh could be done with a refresh token that's no longer valid.
+    async fn refresh_unless_replaced(&self, seen: &Secret) -> Result<bool> {
+        let _refreshing = self.refresh_lock.lock().await;
+
+        if self.token().access_token != *seen {
+            return Ok(false);
+        }
+
+        self.refresh().await?;
+        Ok(true)
+    }
+
+    /// Refresh the token. The caller must hold `refresh_lock`.
+    async fn refresh(&self) -> Result<()> {
+        let Some(refresh_token) = self.token().refresh_token.as_ref().map(RefreshToken::from)
+        else {
             return Err(Error::RefreshUnavailable);
         };
 
-        let token = self
+        let mut token: Token = self
             .oauth
-            .exchange_refresh_token(refresh_token)
+            .exchange_refresh_token(&refresh_token)
             .request_async(async_http_client)
             .await?
-            .set_timestamps();
+            .into();
+
+        // Spotify doesn't always return a new refresh token, in which case the previous one
+        // is still valid and has to be kept (and saved) so the token can be refreshed again.
+        if token.refresh_token.is_none() {
+            token.refresh_token = self.token().refresh_token.clone();
+        }
+
+        // The refreshed token is used even if saving it fails, as the previous refresh token
+        // may no longer be valid (it can be rotated on refresh).
+        let saved = self.token_store.clone().map(|store| (store, token.clone()));
+        self.set_token(token);
+
+        if let Some((store, token)) = saved {
+            run_blocking(move || store.save(&token)).await?;
+        }
 
-        self.auth = token;
         Ok(())
     }
 
+    /// Log out, clearing the token store (if one is set), then replacing the token with an empty,
+    /// expired one for this client and all of its clones (which can't make any more requests afterwards).
+    ///
+    /// If clearing the store fails, the error is returned and the token is left as it is,
+    /// so logging out can be retried.
+    ///
+    /// With the `zeroize` feature, the secrets of the previous token are wiped from memory
+    /// (unless they've been copied elsewhere).
+    ///
+    /// An unauthenticated client is returned, which can authenticate again with the client
+    /// credentials flow. With the other flows, a new client has to be created with `new`,
+    /// as the user has to authorise the app again.
+    ///
+    /// *Note: Spotify doesn't support revoking tokens, so the previous access token stays valid
+    /// until it expires if it has been copied elsewhere.*
+    pub async fn logout(&self) -> Result<Client<UnAuthenticated, F, NoVerifier>> {
+        if let Some(store) = self.token_store.clone() {
+            run_blocking(move || store.clear()).await?;
+        }
+
+        self.set_token(Token::logged_out());
+
+        Ok(Client {
+            auto_refresh: self.auto_refresh,
+            api_url: self.api_url.clone(),
+            auth: Arc::new(RwLock::new(UnAuthenticated)),
+            oauth: self.oauth.clone(),
+            http: self.http.clone(),
+            transport: self.transport.clone(),
+            limiter: self.limiter.clone(),
+            refresh_lock: Arc::default(),
+            refresh_skew: self.refresh_skew,
+            user_id: OnceLock::new(),
+            premium: OnceLock::new(),
+            genre_seeds: self.genre_seeds.clone(),
+            token_store: self.token_store.clone(),
+            verifier: NoVerifier,
+            marker: PhantomData,
+        })
+    }
+
+    /// Refresh the access token if it's about to expire (or already has), returning whether it was refreshed.
+    /// See [`set_refresh_skew`](Self::set_refresh_skew) for how close to its expiry the token has to be.
+    ///
+    /// This is useful for refreshing the token ahead of time (e.g. in a background task),
+    /// so that requests don't have to wait for it to be refreshed.
+    pub async fn refresh_if_needed(&self) -> Result<bool> {
+        let seen = {
+            let token = self.token();
+
+            if token.time_until_expiry() > self.refresh_skew {
+                return Ok(false);
+            }
+
+            token.access_token.clone()
+        };
+
+        self.refresh_unless_replaced(&seen).await
+    }
+
+    /// Refresh the token if it has expired or is about to (and auto refresh is enabled).
+    pub(crate) async fn ensure_valid_token(&self) -> Result<()> {
+        let (expired, expiring, seen) = {
+            let token = self.token();
+            let expiring = token.is_refreshable() && token.time_until_expiry() <= self.refresh_skew;
+
+            (token.is_expired(), expiring, token.access_token.clone())
+        };
+
+        if !expired && !expiring {
+            return Ok(());
+        }
+
+        if self.auto_refresh {
+            self.refresh_unless_replaced(&seen).await.map(|_| ())
+        } else if expired {
+            Err(Error::ExpiredToken)
+        } else {
+            Ok(())
+        }
+    }
+
     pub(crate) async fn request<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         method: Method,
         endpoint: String,
         query: Option<P>,
         body: Option<Body<P>>,
     ) -> Result<T> {
-        if self.auth.is_expired() {
-            if self.auto_refresh {
-                self.request_refresh_token().await?;
-            } else {
-                return Err(Error::ExpiredToken);
+        self.request_with(method, endpoint, query, body, HeaderMap::new())
+            .await
+    }
+
+    /// Same as [`request`](Self::request), but with extra headers added to the request.
+    async fn request_with<Q: Serialize, B: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        endpoint: String,
+        query: Option<Q>,
+        body: Option<Body<B>>,
+        headers: HeaderMap,
+    ) -> Result<T> {
+        self.ensure_valid_token().await?;
+
+        let req = self
+            .build_request(method, format!("{}{endpoint}", self.api_url), query, body)
+            .headers(headers);
+
+        // Kept in case the token turns out to be invalid (e.g. it was revoked or the clock is off),
+        // in which case the token is refreshed and the request is sent again, but only once.
+        let retry = if self.auto_refresh && self.token().is_refreshable() {
+            req.try_clone()
+        } else {
+            None
+        };
+        let seen = self.token().access_token.clone();
+
+        match (self.execute(req).await, retry) {
+            (Err(err), Some(retry)) if err.is_invalid_token() => {
+                // Another request may have refreshed the token in the meantime, in which case
+                // the request is sent again with the new token.
+                self.refresh_unless_replaced(&seen).await?;
+                self.execute(retry).await
             }
+            (res, _) => res,
         }
+    }
+
+    /// Send a request to the given (full) URL, without checking whether the token has expired.
+    pub(crate) async fn send<P: Serialize, T: DeserializeOwned>(
+        &self,
+        method: Method,
+        url: String,
+        query: Option<P>,
+        body: Option<Body<P>>,
+    ) -> Result<T> {
+        self.execute(self.build_request(method, url, query, body))
+            .await
+    }
 
-        let mut req = self
-            .http
-            .request(method, format!("https://api.spotify.com/v1{endpoint}"))
-            .bearer_auth(self.auth.access_token.secret());
+    fn build_request<Q: Serialize, B: Serialize>(
+        &self,
+        method: Method,
+        url: String,
+        query: Option<Q>,
+        body: Option<Body<B>>,
+    ) -> RequestBuilder {
+        let mut req = self.http.request(method, url);
 
         if let Some(q) = query {
             req = req.query(&q);
@@ -287,17 +630,59 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
             req = req.header(CONTENT_LENGTH, 0);
         }
 
-        let res = req.send().await?;
+        req
+    }
+
+    /// Authenticate and send the request, then parse the response.
+    async fn execute<T: DeserializeOwned>(&self, req: RequestBuilder) -> Result<T> {
+        // Held until the response has been read, so that the limit applies to the whole request.
+        let _permit = match &self.limiter {
+            Some(limiter) => limiter.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+
+        let req = req.bearer_auth(&*self.access_token()).build()?;
+
+        let span = tracing::debug_span!(
+            "request",
+            method = %req.method(),
+            path = req.url().path(),
+            status = tracing::field::Empty,
+            request_id = tracing::field::Empty,
+        );
+
+        let res = self.transport.send(req).instrument(span.clone()).await?;
+
+        span.record("status", res.status().as_u16());
+
+        if let Some(request_id) = res.headers().get(REQUEST_ID).and_then(|h| h.to_str().ok()) {
+            span.record("request_id", request_id);
+        }
+
+        tracing::trace!(parent: &span, headers = ?res.headers(), "Received response");
+
+        if res.status() == StatusCode::NO_CONTENT {
+            // Some endpoints return no content at all (e.g. when nothing is playing),
+            // which can't be parsed as JSON, so try deserializing `null` instead.
+            T::deserialize(Value::Null).map_err(|e| Error::deserialization(e, String::new()))
+        } else if res.status().is_success() {
+            let body = res.text().await?;
+
+            // Same as above, for endpoints that return an empty body with a `200 OK`.
+            if body.trim().is_empty() {
+                return T::deserialize(Value::Null).map_err(|e| Error::deserialization(e, body));
+            }
 
-        if res.status().is_success() {
-            Ok(res.json().await?)
+            serde_json::from_str(&body).map_err(|e| Error::deserialization(e, body))
         } else {
-            Err(res.json::<SpotifyError>().await?.into())
+            let err = Error::from_response(res).await;
+            tracing::debug!(parent: &span, error = %err, "Request failed");
+            Err(err)
         }
     }
 
     pub(crate) async fn get<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         endpoint: String,
         query: impl Into<Option<P>>,
     ) -> Result<T> {
@@ -306,7 +691,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub(crate) async fn post<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         endpoint: String,
         body: impl Into<Option<Body<P>>>,
     ) -> Result<T> {
@@ -315,7 +700,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub(crate) async fn put<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         endpoint: String,
         body: impl Into<Option<Body<P>>>,
     ) -> Result<T> {
@@ -323,7 +708,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub(crate) async fn delete<P: Serialize, T: DeserializeOwned>(
-        &mut self,
+        &self,
         endpoint: String,
         body: impl Into<Option<Body<P>>>,
     ) -> Result<T> {
@@ -331,21 +716,86 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
             .await
     }
 
-    fn builder<E: Endpoint>(&mut self, endpoint: E) -> Builder<'_, F, V, E> {
+    /// Send a GET request to the specified endpoint (e.g. `/me/player`), returning the raw JSON response.
+    ///
+    /// This is useful for endpoints or responses that aren't (correctly) covered by the models.
+    pub async fn get_raw<Q: Serialize>(
+        &self,
+        endpoint: impl Into<String>,
+        query: Option<Q>,
+    ) -> Result<Value> {
+        self.get::<Q, _>(endpoint.into(), query).await
+    }
+
+    /// Send a POST request with an optional JSON body to the specified endpoint,
+    /// returning the raw JSON response.
+    pub async fn post_raw<B: Serialize>(
+        &self,
+        endpoint: impl Into<String>,
+        body: Option<B>,
+    ) -> Result<Value> {
+        self.post(endpoint.into(), body.map(Body::Json)).await
+    }
+
+    /// Send a PUT request with an optional JSON body to the specified endpoint,
+    /// returning the raw JSON response.
+    pub async fn put_raw<B: Serialize>(
+        &self,
+        endpoint: impl Into<String>,
+        body: Option<B>,
+    ) -> Result<Value> {
+        self.put(endpoint.into(), body.map(Body::Json)).await
+    }
+
+    /// Send a DELETE request with an optional JSON body to the specified endpoint,
+    /// returning the raw JSON response.
+    pub async fn delete_raw<B: Serialize>(
+        &self,
+        endpoint: impl Into<String>,
+        body: Option<B>,
+    ) -> Result<Value> {
+        self.delete(endpoint.into(), body.map(Body::Json)).await
+    }
+
+    /// Send a request with extra headers to the specified endpoint, returning the raw JSON response.
+    ///
+    /// For example, an `Accept-Language` header can be used to get localised category names.
+    pub async fn request_with_headers<Q: Serialize, B: Serialize>(
+        &self,
+        method: Method,
+        endpoint: impl Into<String>,
+        query: Option<Q>,
+        body: Option<B>,
+        headers: HeaderMap,
+    ) -> Result<Value> {
+        self.request_with(
+            method,
+            endpoint.into(),
+            query,
+            body.map(Body::Json),
+            headers,
+        )
+        .await
+    }
+
+    fn builder<E: Endpoint>(&self, endpoint: E) -> Builder<'_, F, V, E> {
         Builder {
             spotify: self,
             endpoint,
         }
     }
 
-    pub fn album(&mut self, id: impl Into<String>) -> Builder<'_, F, V, AlbumEndpoint> {
+    pub fn album(&self, id: impl Into<String>) -> Builder<'_, F, V, AlbumEndpoint> {
         self.builder(AlbumEndpoint {
             id: id.into(),
             market: None,
         })
     }
 
-    pub fn albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, AlbumsEndpoint> {
+    pub fn albums(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Builder<'_, F, V, AlbumsEndpoint> {
         self.builder(AlbumsEndpoint {
             ids: query_list(ids),
             market: None,
@@ -353,7 +803,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub fn album_tracks(
-        &mut self,
+        &self,
         album_id: impl Into<String>,
     ) -> Builder<'_, F, V, AlbumTracksEndpoint> {
         self.builder(AlbumTracksEndpoint {
@@ -362,30 +812,37 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn new_releases(&mut self) -> Builder<'_, F, V, NewReleasesEndpoint> {
+    /// Get the new album releases featured on Spotify.
+    ///
+    /// Unlike the neighbouring featured playlists and category playlists endpoints,
+    /// this one isn't deprecated.
+    pub fn new_releases(&self) -> Builder<'_, F, V, NewReleasesEndpoint> {
         self.builder(NewReleasesEndpoint::default())
     }
 
-    pub fn artist(&mut self, id: impl Into<String>) -> Builder<'_, F, V, ArtistEndpoint> {
+    pub fn artist(&self, id: impl Into<String>) -> Builder<'_, F, V, ArtistEndpoint> {
         self.builder(ArtistEndpoint { id: id.into() })
     }
 
-    pub async fn get_artists<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<Artist>> {
+    pub async fn get_artists(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<Artist>> {
         self.get("/artists".to_owned(), [("ids", query_list(ids))])
             .await
             .map(|a: Artists| a.artists)
     }
 
-    pub fn audiobook(&mut self, id: impl Into<String>) -> Builder<'_, F, V, AudiobookEndpoint> {
+    pub fn audiobook(&self, id: impl Into<String>) -> Builder<'_, F, V, AudiobookEndpoint> {
         self.builder(AudiobookEndpoint {
             id: id.into(),
             market: None,
         })
     }
 
-    pub fn audiobooks<T: AsRef<str>>(
-        &mut self,
-        ids: &[T],
+    pub fn audiobooks(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
     ) -> Builder<'_, F, V, AudiobooksEndpoint> {
         self.builder(AudiobooksEndpoint {
             ids: query_list(ids),
@@ -394,7 +851,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub fn audiobook_chapters(
-        &mut self,
+        &self,
         audiobook_id: impl Into<String>,
     ) -> Builder<'_, F, V, AudiobookChaptersEndpoint> {
         self.builder(AudiobookChaptersEndpoint {
@@ -404,7 +861,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub fn browse_category(
-        &mut self,
+        &self,
         id: impl Into<String>,
     ) -> Builder<'_, F, V, BrowseCategoryEndpoint> {
         self.builder(BrowseCategoryEndpoint {
@@ -413,12 +870,12 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn browse_categories(&mut self) -> Builder<'_, F, V, BrowseCategoriesEndpoint> {
+    pub fn browse_categories(&self) -> Builder<'_, F, V, BrowseCategoriesEndpoint> {
         self.builder(BrowseCategoriesEndpoint::default())
     }
 
     /// *Note: Spotify's API returns `500 Server error`.*
-    pub fn chapter(&mut self, id: impl Into<String>) -> Builder<'_, F, V, ChapterEndpoint> {
+    pub fn chapter(&self, id: impl Into<String>) -> Builder<'_, F, V, ChapterEndpoint> {
         self.builder(ChapterEndpoint {
             id: id.into(),
             market: None,
@@ -426,48 +883,69 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     /// *Note: Spotify's API returns `500 Server error`.*
-    pub fn chapters<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, ChaptersEndpoint> {
+    pub fn chapters(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Builder<'_, F, V, ChaptersEndpoint> {
         self.builder(ChaptersEndpoint {
             ids: query_list(ids),
             market: None,
         })
     }
 
-    pub fn episode(&mut self, id: impl Into<String>) -> Builder<'_, F, V, EpisodeEndpoint> {
+    pub fn episode(&self, id: impl Into<String>) -> Builder<'_, F, V, EpisodeEndpoint> {
         self.builder(EpisodeEndpoint {
             id: id.into(),
             market: None,
         })
     }
 
-    pub fn episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, EpisodesEndpoint> {
+    pub fn episodes(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Builder<'_, F, V, EpisodesEndpoint> {
         self.builder(EpisodesEndpoint {
             ids: query_list(ids),
             market: None,
         })
     }
 
-    pub async fn get_genre_seeds(&mut self) -> Result<Vec<String>> {
+    pub async fn get_genre_seeds(&self) -> Result<Vec<String>> {
         self.get::<(), _>("/recommendations/available-genre-seeds".to_owned(), None)
             .await
             .map(|g: Genres| g.genres)
     }
 
-    pub async fn get_available_markets(&mut self) -> Result<Vec<String>> {
+    /// Get the genres that can be used as recommendation seeds, which are only requested
+    /// the first time and cached afterwards, as they rarely change.
+    pub async fn available_genre_seeds(&self) -> Result<Vec<String>> {
+        if let Some(genres) = self.genre_seeds.get() {
+            return Ok(genres.clone());
+        }
+
+        let genres = self.get_genre_seeds().await?;
+        // Another request may have cached them in the meantime, in which case they're the same.
+        let _ = self.genre_seeds.set(genres.clone());
+
+        Ok(genres)
+    }
+
+    pub async fn get_available_markets(&self) -> Result<Vec<String>> {
         self.get::<(), _>("/markets".to_owned(), None)
             .await
             .map(|m: Markets| m.markets)
     }
 
-    pub fn playlist(&mut self, id: impl Into<String>) -> Builder<'_, F, V, PlaylistEndpoint> {
+    pub fn playlist(&self, id: impl Into<String>) -> Builder<'_, F, V, PlaylistEndpoint> {
         self.builder(PlaylistEndpoint {
             id: id.into(),
+            additional_types: Some(DEFAULT_ADDITIONAL_TYPES.to_owned()),
             ..Default::default()
         })
     }
 
     pub fn change_playlist_details(
-        &mut self,
+        &self,
         id: impl Into<String>,
     ) -> Builder<'_, F, V, ChangePlaylistDetailsEndpoint> {
         self.builder(ChangePlaylistDetailsEndpoint {
@@ -477,17 +955,18 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub fn playlist_items(
-        &mut self,
+        &self,
         id: impl Into<String>,
     ) -> Builder<'_, F, V, PlaylistItemsEndpoint> {
         self.builder(PlaylistItemsEndpoint {
             id: id.into(),
+            additional_types: Some(DEFAULT_ADDITIONAL_TYPES.to_owned()),
             ..Default::default()
         })
     }
 
     pub fn update_playlist_items(
-        &mut self,
+        &self,
         id: impl Into<String>,
         range_start: u32,
         insert_before: u32,
@@ -500,25 +979,25 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn add_items_to_playlist<T: ToString>(
-        &mut self,
+    pub fn add_items_to_playlist(
+        &self,
         id: impl Into<String>,
-        item_uris: &[T],
+        item_uris: impl IntoIterator<Item = impl ToString>,
     ) -> Builder<'_, F, V, AddPlaylistItemsEndpoint> {
         self.builder(AddPlaylistItemsEndpoint {
             id: id.into(),
-            uris: item_uris.iter().map(ToString::to_string).collect(),
+            uris: item_uris.into_iter().map(|u| u.to_string()).collect(),
             position: None,
         })
     }
 
-    pub fn remove_playlist_items<T: AsRef<str>>(
-        &mut self,
+    pub fn remove_playlist_items(
+        &self,
         id: impl Into<String>,
-        item_uris: &[T],
+        item_uris: impl IntoIterator<Item = impl AsRef<str>>,
     ) -> Builder<'_, F, V, RemovePlaylistItemsEndpoint> {
         let tracks = item_uris
-            .iter()
+            .into_iter()
             .map(|u| json!({ "uri": u.as_ref() }))
             .collect();
 
@@ -529,8 +1008,31 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
+    /// Remove the items at specific positions in the playlist, instead of every occurrence of them
+    /// (which is what [`remove_playlist_items`](Self::remove_playlist_items) does).
+    ///
+    /// Each item is a URI with the (zero-based) positions it should be removed from.
+    /// The positions refer to the playlist as it was in the snapshot with the given ID.
+    pub fn remove_playlist_items_at<T: AsRef<str>, P: AsRef<[u32]>>(
+        &self,
+        id: impl Into<String>,
+        items: &[(T, P)],
+        snapshot_id: impl Into<SnapshotId>,
+    ) -> Builder<'_, F, V, RemovePlaylistItemsEndpoint> {
+        let tracks = items
+            .iter()
+            .map(|(u, p)| json!({ "uri": u.as_ref(), "positions": p.as_ref() }))
+            .collect();
+
+        self.builder(RemovePlaylistItemsEndpoint {
+            id: id.into(),
+            tracks,
+            snapshot_id: Some(snapshot_id.into()),
+        })
+    }
+
     pub fn user_playlists(
-        &mut self,
+        &self,
         user_id: impl Into<String>,
     ) -> Builder<'_, F, V, UserPlaylistsEndpoint> {
         self.builder(UserPlaylistsEndpoint {
@@ -540,10 +1042,10 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub fn create_playlist(
-        &mut self,
+        &self,
         user_id: impl Into<String>,
         name: impl Into<String>,
-    ) -> Builder<'_, F, V, CreatePlaylistEndpoint> {
+    ) -> Builder<'_, F, V, CreatePlaylistEndpoint<'_>> {
         self.builder(CreatePlaylistEndpoint {
             user_id: user_id.into(),
             name: name.into(),
@@ -551,12 +1053,14 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn featured_playlists(&mut self) -> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
+    #[cfg(feature = "deprecated-endpoints")]
+    pub fn featured_playlists(&self) -> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
         self.builder(FeaturedPlaylistsEndpoint::default())
     }
 
+    #[cfg(feature = "deprecated-endpoints")]
     pub fn category_playlists(
-        &mut self,
+        &self,
         category_id: impl Into<String>,
     ) -> Builder<'_, F, V, CategoryPlaylistsEndpoint> {
         self.builder(CategoryPlaylistsEndpoint {
@@ -565,21 +1069,67 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub async fn get_playlist_image(&mut self, id: impl Into<String>) -> Result<Vec<Image>> {
+    pub async fn get_playlist_image(&self, id: impl Into<String>) -> Result<Vec<Image>> {
         self.get::<(), _>(format!("/playlists/{}/images", id.into()), None)
             .await
     }
 
-    pub async fn add_playlist_image(&mut self, id: impl Into<String>, image: &[u8]) -> Result<Nil> {
+    /// Upload a custom cover image for the playlist.
+    ///
+    /// The image has to be a JPEG, and at most 256 KB once Base64 encoded,
+    /// otherwise [`Error::InvalidImage`] is returned.
+    pub async fn add_playlist_image(&self, id: impl Into<String>, image: &[u8]) -> Result<Nil> {
+        if !image.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            return Err(Error::InvalidImage("the image must be a JPEG".to_owned()));
+        }
+
         let encoded_image = general_purpose::STANDARD.encode(image).into_bytes();
+
+        if encoded_image.len() > MAX_IMAGE_SIZE {
+            return Err(Error::InvalidImage(format!(
+                "the Base64 encoded image is {} bytes, but the maximum is {MAX_IMAGE_SIZE} bytes",
+                encoded_image.len()
+            )));
+        }
+
         let body = <Body>::File(encoded_image);
 
         self.put(format!("/playlists/{}/images", id.into()), body)
             .await
     }
 
+    /// Upload a custom cover image for the playlist, reading it from a file.
+    ///
+    /// The same restrictions as [`add_playlist_image`](Self::add_playlist_image) apply.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn add_playlist_image_from_path(
+        &self,
+        id: impl Into<String>,
+        path: impl AsRef<std::path::Path>,
+    ) -> Result<Nil> {
+        let image = tokio::fs::read(path).await?;
+        self.add_playlist_image(id, &image).await
+    }
+
+    /// Upload a custom cover image for the playlist, reading it from an async reader
+    /// (e.g. a [`tokio::fs::File`]) to the end before uploading it.
+    ///
+    /// The same restrictions as [`add_playlist_image`](Self::add_playlist_image) apply.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub async fn add_playlist_image_from_reader(
+        &self,
+        id: impl Into<String>,
+        mut reader: impl tokio::io::AsyncRead + Unpin,
+    ) -> Result<Nil> {
+        use tokio::io::AsyncReadExt;
+
+        let mut image = vec![];
+        reader.read_to_end(&mut image).await?;
+        self.add_playlist_image(id, &image).await
+    }
+
     pub fn search(
-        &mut self,
+        &self,
         query: impl Into<String>,
         item_types: &[Item],
     ) -> Builder<'_, F, V, SearchEndpoint> {
@@ -592,14 +1142,17 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn show(&mut self, id: impl Into<String>) -> Builder<'_, F, V, ShowEndpoint> {
+    pub fn show(&self, id: impl Into<String>) -> Builder<'_, F, V, ShowEndpoint> {
         self.builder(ShowEndpoint {
             id: id.into(),
             market: None,
         })
     }
 
-    pub fn shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, ShowsEndpoint> {
+    pub fn shows(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Builder<'_, F, V, ShowsEndpoint> {
         self.builder(ShowsEndpoint {
             ids: query_list(ids),
             market: None,
@@ -607,7 +1160,7 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
     }
 
     pub fn show_episodes(
-        &mut self,
+        &self,
         show_id: impl Into<String>,
     ) -> Builder<'_, F, V, ShowEpisodesEndpoint> {
         self.builder(ShowEpisodesEndpoint {
@@ -616,47 +1169,50 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn track(&mut self, id: impl Into<String>) -> Builder<'_, F, V, TrackEndpoint> {
+    pub fn track(&self, id: impl Into<String>) -> Builder<'_, F, V, TrackEndpoint> {
         self.builder(TrackEndpoint {
             id: id.into(),
             market: None,
         })
     }
 
-    pub fn tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Builder<'_, F, V, TracksEndpoint> {
+    pub fn tracks(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Builder<'_, F, V, TracksEndpoint> {
         self.builder(TracksEndpoint {
             ids: query_list(ids),
             market: None,
         })
     }
 
-    pub async fn get_track_audio_features(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Result<AudioFeatures> {
+    #[cfg(feature = "deprecated-endpoints")]
+    pub async fn get_track_audio_features(&self, id: impl Into<String>) -> Result<AudioFeatures> {
         self.get::<(), _>(format!("/audio-features/{}", id.into()), None)
             .await
     }
 
-    pub async fn get_tracks_audio_features<T: AsRef<str>>(
-        &mut self,
-        ids: &[T],
-    ) -> Result<Vec<AudioFeatures>> {
+    #[cfg(feature = "deprecated-endpoints")]
+    /// The returned audio features are in the same order as the `ids`, with `None` for the IDs
+    /// that have no audio features (e.g. if they're invalid).
+    pub async fn get_tracks_audio_features(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<Option<AudioFeatures>>> {
         self.get("/audio-features".to_owned(), [("ids", query_list(ids))])
             .await
             .map(|a: AudioFeaturesResult| a.audio_features)
     }
 
-    pub async fn get_track_audio_analysis(
-        &mut self,
-        id: impl Into<String>,
-    ) -> Result<AudioAnalysis> {
+    #[cfg(feature = "deprecated-endpoints")]
+    pub async fn get_track_audio_analysis(&self, id: impl Into<String>) -> Result<AudioAnalysis> {
         self.get::<(), _>(format!("/audio-analysis/{}", id.into()), None)
             .await
     }
 
+    #[cfg(feature = "deprecated-endpoints")]
     pub fn recommendations<S: SeedType, T: AsRef<str>>(
-        &mut self,
+        &self,
         seed: Seed<T, S>,
     ) -> Builder<'_, F, V, RecommendationsEndpoint<S>> {
         let (seed_artists, seed_genres, seed_tracks) = match seed {
@@ -676,15 +1232,15 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub async fn get_user(&mut self, id: impl Into<String>) -> Result<User> {
+    pub async fn get_user(&self, id: impl Into<String>) -> Result<User> {
         self.get::<(), _>(format!("/users/{}", id.into()), None)
             .await
     }
 
-    pub async fn check_if_users_follow_playlist<T: AsRef<str>>(
-        &mut self,
+    pub async fn check_if_users_follow_playlist(
+        &self,
         playlist_id: impl Into<String>,
-        user_ids: &[T],
+        user_ids: impl IntoIterator<Item = impl AsRef<str>>,
     ) -> Result<Vec<bool>> {
         self.get(
             format!("/playlists/{}/followers/contains", playlist_id.into()),
@@ -695,117 +1251,390 @@ impl<F: AuthFlow, V: Verifier> Client<Token, F, V> {
 }
 
 impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
-    pub fn saved_albums(&mut self) -> Builder<'_, F, V, SavedAlbumsEndpoint> {
+    pub fn saved_albums(&self) -> Builder<'_, F, V, SavedAlbumsEndpoint> {
         self.builder(SavedAlbumsEndpoint::default())
     }
 
-    pub async fn save_albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/albums".to_owned(), body_list("ids", ids))
-            .await
+    /// Save the albums to the user's library, in [chunks](Client#library-ids).
+    pub async fn save_albums(&self, ids: impl IntoIterator<Item = impl AsRef<str>>) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_ALBUM_IDS) {
+            self.put::<_, Nil>("/me/albums".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn remove_saved_albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/albums".to_owned(), body_list("ids", ids))
-            .await
+    /// Remove the albums from the user's library, in [chunks](Client#library-ids).
+    pub async fn remove_saved_albums(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_ALBUM_IDS) {
+            self.delete::<_, Nil>("/me/albums".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn check_saved_albums<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get("/me/albums/contains".to_owned(), [("ids", query_list(ids))])
-            .await
+    /// Check whether the albums are saved in the user's library, in [chunks](Client#library-ids).
+    pub async fn check_saved_albums(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<bool>> {
+        let mut checked = Vec::new();
+
+        for ids in id_chunks(ids, MAX_LIBRARY_ALBUM_IDS) {
+            checked.extend(
+                self.get::<_, Vec<bool>>(
+                    "/me/albums/contains".to_owned(),
+                    [("ids", query_list(ids))],
+                )
+                .await?,
+            );
+        }
+
+        Ok(checked)
+    }
+
+    /// Save (or remove) the albums to (or from) the user's library, then check whether
+    /// they're saved, so the new state is returned in a single call.
+    pub async fn set_albums_saved(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+        saved: bool,
+    ) -> Result<Vec<bool>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        if saved {
+            self.save_albums(&ids).await?;
+        } else {
+            self.remove_saved_albums(&ids).await?;
+        }
+
+        self.check_saved_albums(&ids).await
     }
 
-    pub fn saved_audiobooks(&mut self) -> Builder<'_, F, V, SavedAudiobooksEndpoint> {
+    pub fn saved_audiobooks(&self) -> Builder<'_, F, V, SavedAudiobooksEndpoint> {
         self.builder(SavedAudiobooksEndpoint::default())
     }
 
-    pub async fn save_audiobooks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put::<(), _>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
-            .await
+    /// Save the audiobooks to the user's library, in [chunks](Client#library-ids).
+    pub async fn save_audiobooks(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.put::<(), Nil>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn remove_saved_audiobooks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete::<(), _>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
-            .await
+    /// Remove the audiobooks from the user's library, in [chunks](Client#library-ids).
+    pub async fn remove_saved_audiobooks(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.delete::<(), Nil>(format!("/me/audiobooks?ids={}", query_list(ids)), None)
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn check_saved_audiobooks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get(
-            "/me/audiobooks/contains".to_owned(),
-            [("ids", query_list(ids))],
-        )
-        .await
+    /// Check whether the audiobooks are saved in the user's library, in [chunks](Client#library-ids).
+    pub async fn check_saved_audiobooks(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<bool>> {
+        let mut checked = Vec::new();
+
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            checked.extend(
+                self.get::<_, Vec<bool>>(
+                    "/me/audiobooks/contains".to_owned(),
+                    [("ids", query_list(ids))],
+                )
+                .await?,
+            );
+        }
+
+        Ok(checked)
+    }
+
+    /// Save (or remove) the audiobooks to (or from) the user's library, then check whether
+    /// they're saved, so the new state is returned in a single call.
+    pub async fn set_audiobooks_saved(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+        saved: bool,
+    ) -> Result<Vec<bool>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        if saved {
+            self.save_audiobooks(&ids).await?;
+        } else {
+            self.remove_saved_audiobooks(&ids).await?;
+        }
+
+        self.check_saved_audiobooks(&ids).await
     }
 
-    pub fn saved_episodes(&mut self) -> Builder<'_, F, V, SavedEpisodesEndpoint> {
+    pub fn saved_episodes(&self) -> Builder<'_, F, V, SavedEpisodesEndpoint> {
         self.builder(SavedEpisodesEndpoint::default())
     }
 
-    pub async fn save_episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/episodes".to_owned(), body_list("ids", ids))
-            .await
+    /// Save the episodes to the user's library, in [chunks](Client#library-ids).
+    pub async fn save_episodes(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.put::<_, Nil>("/me/episodes".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn remove_saved_episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/episodes".to_owned(), body_list("ids", ids))
-            .await
+    /// Remove the episodes from the user's library, in [chunks](Client#library-ids).
+    pub async fn remove_saved_episodes(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.delete::<_, Nil>("/me/episodes".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn check_saved_episodes<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get::<(), _>(
-            format!("/me/episodes/contains?ids={}", query_list(ids)),
-            None,
-        )
-        .await
+    /// Check whether the episodes are saved in the user's library, in [chunks](Client#library-ids).
+    pub async fn check_saved_episodes(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<bool>> {
+        let mut checked = Vec::new();
+
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            checked.extend(
+                self.get::<(), Vec<bool>>(
+                    format!("/me/episodes/contains?ids={}", query_list(ids)),
+                    None,
+                )
+                .await?,
+            );
+        }
+
+        Ok(checked)
     }
 
-    pub fn current_user_playlists(&mut self) -> Builder<'_, F, V, CurrentUserPlaylistsEndpoint> {
+    /// Save (or remove) the episodes to (or from) the user's library, then check whether
+    /// they're saved, so the new state is returned in a single call.
+    pub async fn set_episodes_saved(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+        saved: bool,
+    ) -> Result<Vec<bool>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        if saved {
+            self.save_episodes(&ids).await?;
+        } else {
+            self.remove_saved_episodes(&ids).await?;
+        }
+
+        self.check_saved_episodes(&ids).await
+    }
+
+    pub fn current_user_playlists(&self) -> Builder<'_, F, V, CurrentUserPlaylistsEndpoint> {
         self.builder(CurrentUserPlaylistsEndpoint::default())
     }
 
-    pub fn saved_shows(&mut self) -> Builder<'_, F, V, SavedShowsEndpoint> {
+    pub fn saved_shows(&self) -> Builder<'_, F, V, SavedShowsEndpoint> {
         self.builder(SavedShowsEndpoint::default())
     }
 
-    pub async fn save_shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/shows".to_owned(), body_list("ids", ids))
-            .await
+    /// Save the shows to the user's library, in [chunks](Client#library-ids).
+    pub async fn save_shows(&self, ids: impl IntoIterator<Item = impl AsRef<str>>) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.put::<_, Nil>("/me/shows".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn remove_saved_shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/shows".to_owned(), body_list("ids", ids))
-            .await
+    /// Remove the shows from the user's library, in [chunks](Client#library-ids).
+    pub async fn remove_saved_shows(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.delete::<_, Nil>("/me/shows".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn check_saved_shows<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get("/me/shows/contains".to_owned(), [("ids", query_list(ids))])
-            .await
+    /// Check whether the shows are saved in the user's library, in [chunks](Client#library-ids).
+    pub async fn check_saved_shows(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<bool>> {
+        let mut checked = Vec::new();
+
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            checked.extend(
+                self.get::<_, Vec<bool>>(
+                    "/me/shows/contains".to_owned(),
+                    [("ids", query_list(ids))],
+                )
+                .await?,
+            );
+        }
+
+        Ok(checked)
+    }
+
+    /// Save (or remove) the shows to (or from) the user's library, then check whether
+    /// they're saved, so the new state is returned in a single call.
+    pub async fn set_shows_saved(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+        saved: bool,
+    ) -> Result<Vec<bool>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        if saved {
+            self.save_shows(&ids).await?;
+        } else {
+            self.remove_saved_shows(&ids).await?;
+        }
+
+        self.check_saved_shows(&ids).await
     }
 
-    pub fn saved_tracks(&mut self) -> Builder<'_, F, V, SavedTracksEndpoint> {
+    pub fn saved_tracks(&self) -> Builder<'_, F, V, SavedTracksEndpoint> {
         self.builder(SavedTracksEndpoint::default())
     }
 
-    pub async fn save_tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.put("/me/tracks".to_owned(), body_list("ids", ids))
-            .await
+    /// Save the tracks to the user's library, in [chunks](Client#library-ids).
+    pub async fn save_tracks(&self, ids: impl IntoIterator<Item = impl AsRef<str>>) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.put::<_, Nil>("/me/tracks".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn remove_saved_tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Nil> {
-        self.delete("/me/tracks".to_owned(), body_list("ids", ids))
-            .await
+    /// Remove the tracks from the user's library, in [chunks](Client#library-ids).
+    pub async fn remove_saved_tracks(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            self.delete::<_, Nil>("/me/tracks".to_owned(), body_list("ids", ids))
+                .await?;
+        }
+
+        Ok(Nil)
     }
 
-    pub async fn check_saved_tracks<T: AsRef<str>>(&mut self, ids: &[T]) -> Result<Vec<bool>> {
-        self.get("/me/tracks/contains".to_owned(), [("ids", query_list(ids))])
-            .await
+    /// Check whether the tracks are saved in the user's library, in [chunks](Client#library-ids).
+    pub async fn check_saved_tracks(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<bool>> {
+        let mut checked = Vec::new();
+
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            checked.extend(
+                self.get::<_, Vec<bool>>(
+                    "/me/tracks/contains".to_owned(),
+                    [("ids", query_list(ids))],
+                )
+                .await?,
+            );
+        }
+
+        Ok(checked)
     }
 
-    pub async fn get_current_user_profile(&mut self) -> Result<User> {
-        self.get::<(), _>("/me".to_owned(), None).await
+    /// Save (or remove) the tracks to (or from) the user's library, then check whether
+    /// they're saved, so the new state is returned in a single call.
+    pub async fn set_tracks_saved(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+        saved: bool,
+    ) -> Result<Vec<bool>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        if saved {
+            self.save_tracks(&ids).await?;
+        } else {
+            self.remove_saved_tracks(&ids).await?;
+        }
+
+        self.check_saved_tracks(&ids).await
+    }
+
+    pub async fn get_current_user_profile(&self) -> Result<PrivateUser> {
+        self.get::<(), _>("/me".to_owned(), None).await
+    }
+
+    /// Get the current user's ID, which is only requested the first time and cached afterwards.
+    pub async fn current_user_id(&self) -> Result<String> {
+        if let Some(id) = self.user_id.get() {
+            return Ok(id.clone());
+        }
+
+        let user = self.cache_current_user().await?;
+        Ok(user.id)
+    }
+
+    /// Returns whether the current user has a Premium subscription, which most of the
+    /// player endpoints require. It's only requested the first time and cached afterwards.
+    ///
+    /// This is `None` if the user-read-private scope wasn't granted, in which case it isn't cached.
+    pub async fn is_premium(&self) -> Result<Option<bool>> {
+        if let Some(premium) = self.premium.get() {
+            return Ok(Some(*premium));
+        }
+
+        let user = self.cache_current_user().await?;
+        Ok(user.product.map(|product| product == Product::Premium))
+    }
+
+    async fn cache_current_user(&self) -> Result<PrivateUser> {
+        let user = self.get_current_user_profile().await?;
+        let _ = self.user_id.set(user.id.clone());
+
+        if let Some(product) = user.product {
+            let _ = self.premium.set(product == Product::Premium);
+        }
+
+        Ok(user)
+    }
+
+    /// Create a playlist for the current user, so their ID doesn't have to be fetched separately.
+    pub async fn create_playlist_for_me(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<Builder<'_, F, V, CreatePlaylistEndpoint<'_>>> {
+        let user_id = self.current_user_id().await?;
+        Ok(self.create_playlist(user_id, name))
     }
 
     pub fn current_user_top_items(
-        &mut self,
+        &self,
         r#type: UserItemType,
     ) -> Builder<'_, F, V, UserTopItemsEndpoint> {
         self.builder(UserTopItemsEndpoint {
@@ -815,7 +1644,7 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
     }
 
     pub fn follow_playlist(
-        &mut self,
+        &self,
         id: impl Into<String>,
     ) -> Builder<'_, F, V, FollowPlaylistBuilder> {
         self.builder(FollowPlaylistBuilder {
@@ -824,12 +1653,12 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub async fn unfollow_playlist(&mut self, id: impl Into<String>) -> Result<Nil> {
+    pub async fn unfollow_playlist(&self, id: impl Into<String>) -> Result<Nil> {
         self.delete::<(), _>(format!("/playlists/{}/followers", id.into()), None)
             .await
     }
 
-    pub fn followed_artists(&mut self) -> Builder<'_, F, V, FollowedArtistsBuilder> {
+    pub fn followed_artists(&self) -> Builder<'_, F, V, FollowedArtistsBuilder> {
         // Currently only the "artist" type is supported, so it's hardcoded.
         self.builder(FollowedArtistsBuilder {
             r#type: "artist".to_owned(),
@@ -837,34 +1666,189 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn follow_artists<T: AsRef<str>>(
-        &mut self,
-        ids: &[T],
+    pub fn follow_artists(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
     ) -> Builder<'_, F, V, FollowUserOrArtistEndpoint> {
         self.builder(FollowUserOrArtistEndpoint {
             r#type: "artist".to_owned(),
-            ids: ids.iter().map(|i| i.as_ref().to_owned()).collect(),
+            ids: ids.into_iter().map(|i| i.as_ref().to_owned()).collect(),
         })
     }
 
-    pub fn follow_users<T: AsRef<str>>(
-        &mut self,
-        ids: &[T],
+    pub fn follow_users(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
     ) -> Builder<'_, F, V, FollowUserOrArtistEndpoint> {
         self.builder(FollowUserOrArtistEndpoint {
             r#type: "user".to_owned(),
-            ids: ids.iter().map(|i| i.as_ref().to_owned()).collect(),
+            ids: ids.into_iter().map(|i| i.as_ref().to_owned()).collect(),
         })
     }
 
-    pub async fn get_playback_state(&mut self, market: Option<&str>) -> Result<PlaybackState> {
-        let market = market.map(|m| [("market", m)]);
-        self.get::<[(&str, &str); 1], _>("/me/player".to_owned(), market)
+    pub async fn unfollow_artists(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        self.delete(
+            "/me/following?type=artist".to_owned(),
+            body_list("ids", ids),
+        )
+        .await
+    }
+
+    pub async fn unfollow_users(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Nil> {
+        self.delete("/me/following?type=user".to_owned(), body_list("ids", ids))
+            .await
+    }
+
+    /// Check whether the user follows the artists, in [chunks](Client#library-ids).
+    pub async fn check_if_user_follows_artists(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<bool>> {
+        let mut checked = Vec::new();
+
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            checked.extend(
+                self.get::<_, Vec<bool>>(
+                    "/me/following/contains".to_owned(),
+                    [("type", "artist".to_owned()), ("ids", query_list(ids))],
+                )
+                .await?,
+            );
+        }
+
+        Ok(checked)
+    }
+
+    /// Follow (or unfollow) the artists, then check whether the user follows them,
+    /// so the new state is returned in a single call.
+    pub async fn set_artists_followed(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+        followed: bool,
+    ) -> Result<Vec<bool>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        if followed {
+            self.follow_artists(&ids).follow().await?;
+        } else {
+            self.unfollow_artists(&ids).await?;
+        }
+
+        self.check_if_user_follows_artists(&ids).await
+    }
+
+    /// Check whether the user follows the users, in [chunks](Client#library-ids).
+    pub async fn check_if_user_follows_users(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Result<Vec<bool>> {
+        let mut checked = Vec::new();
+
+        for ids in id_chunks(ids, MAX_LIBRARY_IDS) {
+            checked.extend(
+                self.get::<_, Vec<bool>>(
+                    "/me/following/contains".to_owned(),
+                    [("type", "user".to_owned()), ("ids", query_list(ids))],
+                )
+                .await?,
+            );
+        }
+
+        Ok(checked)
+    }
+
+    /// Follow (or unfollow) the users, then check whether the user follows them,
+    /// so the new state is returned in a single call.
+    pub async fn set_users_followed(
+        &self,
+        ids: impl IntoIterator<Item = impl AsRef<str>>,
+        followed: bool,
+    ) -> Result<Vec<bool>> {
+        let ids: Vec<_> = ids.into_iter().collect();
+
+        if followed {
+            self.follow_users(&ids).follow().await?;
+        } else {
+            self.unfollow_users(&ids).await?;
+        }
+
+        self.check_if_user_follows_users(&ids).await
+    }
+
+    /// Get the current playback state, which is `None` if nothing is playing.
+    ///
+    /// Both tracks and episodes are requested, so a playing podcast episode is returned
+    /// as [`PlayableItem::Episode`](crate::model::PlayableItem::Episode) rather than `null`.
+    pub async fn get_playback_state(&self, market: Option<&str>) -> Result<Option<PlaybackState>> {
+        self.get("/me/player".to_owned(), playback_query(market))
             .await
     }
 
+    /// Get the repeat mode of the current playback, which is `None` if nothing is playing.
+    pub async fn current_repeat_mode(&self) -> Result<Option<RepeatMode>> {
+        let state = self.get_playback_state(None).await?;
+        Ok(state.and_then(|s| s.repeat_mode()))
+    }
+
+    /// Get whether the current playback is shuffled, which is `None` if nothing is playing.
+    pub async fn current_shuffle_state(&self) -> Result<Option<bool>> {
+        let state = self.get_playback_state(None).await?;
+        Ok(state.and_then(|s| s.shuffle_state))
+    }
+
+    /// Poll the playback state every `interval`, yielding `None` when nothing is playing.
+    ///
+    /// If `only_changes` is `true`, a state is only yielded when the playing item, the playing state
+    /// or the position (e.g. after seeking) changes. The first state is always yielded.
+    pub fn playback_stream(
+        &self,
+        interval: Duration,
+        only_changes: bool,
+    ) -> impl Stream<Item = Result<Option<PlaybackState>>> + '_ {
+        stream::unfold(
+            (self, None::<Option<PlaybackState>>, false),
+            move |(spotify, mut last, started)| async move {
+                if started {
+                    sleep(interval).await;
+                }
+
+                loop {
+                    let state = match spotify.get_playback_state(None).await {
+                        Ok(state) => state,
+                        Err(err) => return Some((Err(err), (spotify, last, true))),
+                    };
+
+                    let changed = match (&last, &state) {
+                        (None, _) => true,
+                        (Some(Some(previous)), Some(current)) => {
+                            current.changed_since(previous, interval)
+                        }
+                        (Some(previous), current) => previous.is_some() != current.is_some(),
+                    };
+
+                    last = Some(state.clone());
+
+                    if changed || !only_changes {
+                        return Some((Ok(state), (spotify, last, true)));
+                    }
+
+                    sleep(interval).await;
+                }
+            },
+        )
+    }
+
+    /// Transfer the playback to the given device.
+    ///
+    /// Only a single device is supported by Spotify.
     pub fn transfer_playback(
-        &mut self,
+        &self,
         device_id: impl Into<String>,
     ) -> Builder<'_, F, V, TransferPlaybackEndpoint> {
         self.builder(TransferPlaybackEndpoint {
@@ -873,38 +1857,135 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub async fn get_available_devices(&mut self) -> Result<Vec<Device>> {
+    pub async fn get_available_devices(&self) -> Result<Vec<Device>> {
         self.get::<(), _>("/me/player/devices".to_owned(), None)
             .await
             .map(|d: Devices| d.devices)
     }
 
+    /// Get the currently playing item, which is `None` if nothing is playing.
+    ///
+    /// Like [`get_playback_state`](Self::get_playback_state), this includes podcast episodes.
     pub async fn get_currently_playing_track(
-        &mut self,
+        &self,
         market: Option<&str>,
-    ) -> Result<PlaybackState> {
-        let market = market.map(|m| [("market", m)]);
-        self.get::<Option<[(&str, &str); 1]>, _>("/me/player/currently-playing".to_owned(), market)
-            .await
+    ) -> Result<Option<PlaybackState>> {
+        self.get(
+            "/me/player/currently-playing".to_owned(),
+            playback_query(market),
+        )
+        .await
     }
 
-    pub fn start_playback(&mut self) -> Builder<'_, F, V, StartPlaybackEndpoint> {
+    pub fn start_playback(&self) -> Builder<'_, F, V, StartPlaybackEndpoint> {
         self.builder(StartPlaybackEndpoint::default())
     }
 
-    pub async fn pause_playback(&mut self, device_id: Option<&str>) -> Result<Nil> {
+    /// Play the track with the given *URI* right away, replacing the current playback.
+    ///
+    /// [`TrackUri`](crate::model::uri::TrackUri) can be used to create it from an ID.
+    pub async fn play_track(
+        &self,
+        track_uri: impl AsRef<str>,
+        device_id: Option<&str>,
+    ) -> Result<Nil> {
+        self.start_playback_on(device_id)
+            .uris([track_uri])
+            .send()
+            .await
+    }
+
+    /// Play the album with the given ID from the start.
+    pub async fn play_album(
+        &self,
+        album_id: impl AsRef<str>,
+        device_id: Option<&str>,
+    ) -> Result<Nil> {
+        self.play_context(ContextUri::album(album_id), device_id)
+            .await
+    }
+
+    /// Play the playlist with the given ID from the start.
+    pub async fn play_playlist(
+        &self,
+        playlist_id: impl AsRef<str>,
+        device_id: Option<&str>,
+    ) -> Result<Nil> {
+        self.play_context(ContextUri::playlist(playlist_id), device_id)
+            .await
+    }
+
+    /// Play the context (an album, artist, playlist or show) with the given *URI* from the start.
+    ///
+    /// [`ContextUri`] can be used to create it from an ID.
+    pub async fn play_context(
+        &self,
+        context_uri: impl Into<String>,
+        device_id: Option<&str>,
+    ) -> Result<Nil> {
+        self.start_playback_on(device_id)
+            .context_uri(context_uri)
+            .send()
+            .await
+    }
+
+    fn start_playback_on(
+        &self,
+        device_id: Option<&str>,
+    ) -> Builder<'_, F, V, StartPlaybackEndpoint> {
+        let start = self.start_playback();
+
+        match device_id {
+            Some(device_id) => start.device_id(device_id),
+            None => start,
+        }
+    }
+
+    pub async fn pause_playback(&self, device_id: Option<&str>) -> Result<Nil> {
         let device_id = device_id.map(|d| [("device_id", d)]);
         self.request(Method::PUT, "/me/player/pause".to_owned(), device_id, None)
             .await
     }
 
-    pub async fn skip_to_next(&mut self, device_id: Option<&str>) -> Result<Nil> {
+    /// Resume the playback, unless it's already playing (on the given device, if one is specified),
+    /// returning whether the playback was resumed.
+    ///
+    /// Resuming playback that is already playing fails on some devices, so this checks the
+    /// playback state first.
+    pub async fn ensure_playing(&self, device_id: Option<&str>) -> Result<bool> {
+        let state = self.get_playback_state(None).await?;
+
+        if is_playing_on(state.as_ref(), device_id) {
+            return Ok(false);
+        }
+
+        self.start_playback_on(device_id).send().await?;
+        Ok(true)
+    }
+
+    /// Pause the playback, unless it's already paused or nothing is playing (on the given device,
+    /// if one is specified), returning whether the playback was paused.
+    ///
+    /// Pausing playback that is already paused fails on some devices, so this checks the
+    /// playback state first.
+    pub async fn ensure_paused(&self, device_id: Option<&str>) -> Result<bool> {
+        let state = self.get_playback_state(None).await?;
+
+        if !is_playing_on(state.as_ref(), device_id) {
+            return Ok(false);
+        }
+
+        self.pause_playback(device_id).await?;
+        Ok(true)
+    }
+
+    pub async fn skip_to_next(&self, device_id: Option<&str>) -> Result<Nil> {
         let device_id = device_id.map(|d| [("device_id", d)]);
         self.request(Method::POST, "/me/player/next".to_owned(), device_id, None)
             .await
     }
 
-    pub async fn skip_to_previous(&mut self, device_id: Option<&str>) -> Result<Nil> {
+    pub async fn skip_to_previous(&self, device_id: Option<&str>) -> Result<Nil> {
         let device_id = device_id.map(|d| [("device_id", d)]);
         self.request(
             Method::POST,
@@ -915,16 +1996,51 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
         .await
     }
 
-    pub fn seek_to_position(&mut self, position: u32) -> Builder<'_, F, V, SeekToPositionEndpoint> {
+    pub fn seek_to_position(&self, position: u32) -> Builder<'_, F, V, SeekToPositionEndpoint> {
         self.builder(SeekToPositionEndpoint {
             position_ms: position,
             device_id: None,
         })
     }
 
+    /// Seek forward (or backward, if `delta_ms` is negative) from the current position
+    /// in the currently playing item.
+    ///
+    /// The new position is clamped to the item's duration. If `skip_past_end` is `true`,
+    /// seeking past the end skips to the next item instead.
+    ///
+    /// Returns [`Error::NothingPlaying`] if nothing is currently playing.
+    pub async fn seek_relative(
+        &self,
+        delta_ms: i64,
+        device_id: Option<&str>,
+        skip_past_end: bool,
+    ) -> Result<Nil> {
+        let state = self.get_playback_state(None).await?;
+        let Some((progress, item)) = state
+            .as_ref()
+            .and_then(|s| s.progress_ms.zip(s.item.as_ref()))
+        else {
+            return Err(Error::NothingPlaying);
+        };
+
+        let Some(position) = relative_position(progress, item.duration(), delta_ms, skip_past_end)
+        else {
+            return self.skip_to_next(device_id).await;
+        };
+
+        let mut seek = self.seek_to_position(position);
+
+        if let Some(device_id) = device_id {
+            seek = seek.device_id(device_id);
+        }
+
+        seek.send().await
+    }
+
     /// *Note: This endpoint seems to be broken, returning 403 Forbidden "Player command failed: Restriction violated"*
     pub fn set_repeat_mode(
-        &mut self,
+        &self,
         repeat_mode: RepeatMode,
     ) -> Builder<'_, F, V, SetRepeatModeEndpoint> {
         self.builder(SetRepeatModeEndpoint {
@@ -933,10 +2049,7 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn set_playback_volume(
-        &mut self,
-        volume: u32,
-    ) -> Builder<'_, F, V, SetPlaybackVolumeEndpoint> {
+    pub fn set_playback_volume(&self, volume: u32) -> Builder<'_, F, V, SetPlaybackVolumeEndpoint> {
         self.builder(SetPlaybackVolumeEndpoint {
             volume_percent: volume,
             device_id: None,
@@ -945,7 +2058,7 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
 
     /// *Note: This endpoint seems to be broken, returning 403 Forbidden "Player command failed: Restriction violated"*
     pub fn toggle_playback_shuffle(
-        &mut self,
+        &self,
         shuffle: bool,
     ) -> Builder<'_, F, V, ToggleShuffleEndpoint> {
         self.builder(ToggleShuffleEndpoint {
@@ -954,16 +2067,16 @@ impl<F: AuthFlow + Authorised, V: Verifier> Client<Token, F, V> {
         })
     }
 
-    pub fn recently_played_tracks(&mut self) -> Builder<'_, F, V, RecentlyPlayedTracksEndpoint> {
+    pub fn recently_played_tracks(&self) -> Builder<'_, F, V, RecentlyPlayedTracksEndpoint> {
         self.builder(RecentlyPlayedTracksEndpoint::default())
     }
 
-    pub async fn get_user_queue(&mut self) -> Result<Queue> {
+    pub async fn get_user_queue(&self) -> Result<Queue> {
         self.get::<(), _>("/me/player/queue".to_owned(), None).await
     }
 
     pub fn add_item_to_queue(
-        &mut self,
+        &self,
         uri: impl Into<String>,
     ) -> Builder<'_, F, V, AddItemToQueueEndpoint> {
         self.builder(AddItemToQueueEndpoint {
@@ -990,18 +2103,27 @@ impl Client<UnAuthenticated, AuthCodeFlow, CsrfVerifier> {
             return Err(Error::InvalidStateParameter);
         }
 
-        let token = self
+        let token: Token = self
             .oauth
             .exchange_code(AuthorizationCode::new(auth_code))
             .request_async(async_http_client)
             .await?
-            .set_timestamps();
+            .into();
 
         Ok(Client {
             auto_refresh: self.auto_refresh,
-            auth: token,
+            api_url: self.api_url,
+            auth: Arc::new(RwLock::new(token)),
             oauth: self.oauth,
             http: self.http,
+            transport: self.transport,
+            limiter: self.limiter,
+            refresh_lock: self.refresh_lock,
+            refresh_skew: self.refresh_skew,
+            user_id: OnceLock::new(),
+            premium: OnceLock::new(),
+            genre_seeds: OnceLock::new(),
+            token_store: None,
             verifier: NoVerifier,
             marker: PhantomData,
         })
@@ -1025,19 +2147,28 @@ impl Client<UnAuthenticated, AuthCodePkceFlow, PkceVerifier> {
             return Err(Error::InvalidStateParameter);
         }
 
-        let token = self
+        let token: Token = self
             .oauth
             .exchange_code(AuthorizationCode::new(auth_code))
             .set_pkce_verifier(self.verifier.pkce_verifier)
             .request_async(async_http_client)
             .await?
-            .set_timestamps();
+            .into();
 
         Ok(Client {
             auto_refresh: self.auto_refresh,
-            auth: token,
+            api_url: self.api_url,
+            auth: Arc::new(RwLock::new(token)),
             oauth: self.oauth,
             http: self.http,
+            transport: self.transport,
+            limiter: self.limiter,
+            refresh_lock: self.refresh_lock,
+            refresh_skew: self.refresh_skew,
+            user_id: OnceLock::new(),
+            premium: OnceLock::new(),
+            genre_seeds: OnceLock::new(),
+            token_store: None,
             verifier: NoVerifier,
             marker: PhantomData,
         })
@@ -1051,31 +2182,772 @@ impl Client<UnAuthenticated, ClientCredsFlow, NoVerifier> {
     /// This authentication method doesn't allow for token refreshing or to access
     /// user resources.
     pub async fn authenticate(
-        ClientCredsFlow {
-            client_id,
-            client_secret,
-        }: ClientCredsFlow,
+        auth_flow: ClientCredsFlow,
     ) -> Result<Client<Token, ClientCredsFlow, NoVerifier>> {
         let oauth = OAuthClient::new(
-            ClientId::new(client_id),
-            Some(ClientSecret::new(client_secret)),
-            AuthUrl::new(AUTHORISATION_URL.to_owned()).unwrap(),
-            Some(TokenUrl::new(TOKEN_URL.to_owned()).unwrap()),
+            auth_flow.client_id(),
+            auth_flow.client_secret(),
+            auth_flow.auth_url(),
+            Some(auth_flow.token_url()),
         );
 
-        let token = oauth
+        let token: Token = oauth
             .exchange_client_credentials()
             .request_async(async_http_client)
             .await?
-            .set_timestamps();
+            .into();
 
         Ok(Client {
             auto_refresh: false,
-            auth: token,
+            api_url: API_URL.to_owned(),
+            auth: Arc::new(RwLock::new(token)),
             oauth,
             http: reqwest::Client::new(),
+            transport: Arc::new(reqwest::Client::new()),
+            limiter: None,
+            refresh_lock: Arc::default(),
+            refresh_skew: DEFAULT_REFRESH_SKEW,
+            user_id: OnceLock::new(),
+            premium: OnceLock::new(),
+            genre_seeds: OnceLock::new(),
+            token_store: None,
             verifier: NoVerifier,
             marker: PhantomData,
         })
     }
 }
+
+/// Sleep using tokio's timer, or the browser's timer on wasm32 (where there's no tokio runtime).
+async fn sleep(duration: Duration) {
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+}
+
+/// Run blocking work (such as the token store's file I/O) without blocking the async runtime.
+async fn run_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    #[cfg(not(target_arch = "wasm32"))]
+    return tokio::task::spawn_blocking(f)
+        .await
+        .unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic()));
+
+    #[cfg(target_arch = "wasm32")]
+    f()
+}
+
+/// Split the IDs into chunks of at most `size`, as Spotify limits how many can be sent at once.
+fn id_chunks(ids: impl IntoIterator<Item = impl AsRef<str>>, size: usize) -> Vec<Vec<String>> {
+    let ids: Vec<_> = ids.into_iter().map(|id| id.as_ref().to_owned()).collect();
+    ids.chunks(size).map(<[String]>::to_vec).collect()
+}
+
+/// The query for the playback state endpoints, which also requests episodes.
+fn playback_query(market: Option<&str>) -> Vec<(&'static str, &str)> {
+    let mut query = vec![("additional_types", DEFAULT_ADDITIONAL_TYPES)];
+    query.extend(market.map(|m| ("market", m)));
+    query
+}
+
+/// Whether the playback is playing on the given device, or on any device if none is given.
+fn is_playing_on(state: Option<&PlaybackState>, device_id: Option<&str>) -> bool {
+    state.is_some_and(|s| {
+        let device = s.device.as_ref().and_then(|d| d.id.as_deref());
+        s.is_playing && (device_id.is_none() || device == device_id)
+    })
+}
+
+/// The position `delta_ms` away from `progress_ms`, clamped to `[0, duration]`, or `None` if it's
+/// past the end and `skip_past_end` is `true` (meaning the next item should be played instead).
+fn relative_position(
+    progress_ms: u32,
+    duration: Duration,
+    delta_ms: i64,
+    skip_past_end: bool,
+) -> Option<u32> {
+    let duration = i64::try_from(duration.as_millis()).unwrap_or(i64::MAX);
+    let position = i64::from(progress_ms).saturating_add(delta_ms);
+
+    if skip_past_end && position >= duration {
+        return None;
+    }
+
+    Some(u32::try_from(position.clamp(0, duration)).unwrap_or(u32::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::TransportFuture;
+
+    fn token(access_token: &str) -> Token {
+        serde_json::from_value::<Token>(json!({
+            "access_token": access_token,
+            "refresh_token": "refresh",
+            "token_type": "Bearer",
+            "expires_in": 3600,
+        }))
+        .unwrap()
+        .set_timestamps()
+    }
+
+    #[derive(Debug)]
+    struct StaticTransport {
+        status: u16,
+        body: &'static str,
+    }
+
+    impl HttpTransport for StaticTransport {
+        fn send(&self, _request: reqwest::Request) -> TransportFuture<'_> {
+            let res = http::Response::builder()
+                .status(self.status)
+                .body(self.body)
+                .unwrap();
+
+            Box::pin(async move { Ok(res.into()) })
+        }
+    }
+
+    /// Responds with the same body to every request, recording the requested URLs.
+    #[derive(Debug)]
+    struct RecordingTransport {
+        body: &'static str,
+        urls: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+
+    impl HttpTransport for RecordingTransport {
+        fn send(&self, request: reqwest::Request) -> TransportFuture<'_> {
+            self.urls.lock().unwrap().push(request.url().to_string());
+
+            let res = http::Response::builder()
+                .status(200)
+                .body(self.body)
+                .unwrap();
+
+            Box::pin(async move { Ok(res.into()) })
+        }
+    }
+
+    /// Records the number of IDs in the body (or query) of each request, answering checks
+    /// (`GET` requests) with `true` for each ID.
+    #[derive(Debug)]
+    struct IdCountTransport(Arc<std::sync::Mutex<Vec<usize>>>);
+
+    impl HttpTransport for IdCountTransport {
+        fn send(&self, request: reqwest::Request) -> TransportFuture<'_> {
+            let count = match request.body().and_then(reqwest::Body::as_bytes) {
+                Some(body) => {
+                    let body: Value = serde_json::from_slice(body).unwrap();
+                    body["ids"].as_array().unwrap().len()
+                }
+                None => {
+                    let (_, ids) = request
+                        .url()
+                        .query_pairs()
+                        .find(|(k, _)| k == "ids")
+                        .unwrap();
+                    ids.split(',').count()
+                }
+            };
+            self.0.lock().unwrap().push(count);
+
+            let body = if request.method() == Method::GET {
+                serde_json::to_string(&vec![true; count]).unwrap()
+            } else {
+                String::new()
+            };
+            let res = http::Response::builder().status(200).body(body).unwrap();
+
+            Box::pin(async move { Ok(res.into()) })
+        }
+    }
+
+    fn client() -> Client<Token, AuthCodeFlow, NoVerifier> {
+        Client {
+            auto_refresh: true,
+            api_url: API_URL.to_owned(),
+            auth: Arc::new(RwLock::new(token("first"))),
+            oauth: OAuthClient::new(
+                ClientId::new("id".to_owned()),
+                None,
+                oauth2::AuthUrl::new("https://example.com/authorize".to_owned()).unwrap(),
+                None,
+            ),
+            http: reqwest::Client::new(),
+            transport: Arc::new(reqwest::Client::new()),
+            limiter: None,
+            refresh_lock: Arc::default(),
+            refresh_skew: DEFAULT_REFRESH_SKEW,
+            user_id: OnceLock::new(),
+            premium: OnceLock::new(),
+            genre_seeds: OnceLock::new(),
+            token_store: None,
+            verifier: NoVerifier,
+            marker: PhantomData,
+        }
+    }
+
+    /// Records whether the store was cleared.
+    #[derive(Debug)]
+    struct ClearedStore(Arc<std::sync::atomic::AtomicBool>);
+
+    impl TokenStore for ClearedStore {
+        fn load(&self) -> std::io::Result<Option<Token>> {
+            Ok(None)
+        }
+
+        fn save(&self, _token: &Token) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn clear(&self) -> std::io::Result<()> {
+            self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// Keeps the last token saved to it.
+    #[derive(Debug, Default)]
+    struct MemoryStore(std::sync::Mutex<Option<Token>>);
+
+    impl TokenStore for Arc<MemoryStore> {
+        fn load(&self) -> std::io::Result<Option<Token>> {
+            Ok(self.0.lock().unwrap().clone())
+        }
+
+        fn save(&self, token: &Token) -> std::io::Result<()> {
+            *self.0.lock().unwrap() = Some(token.clone());
+            Ok(())
+        }
+
+        fn clear(&self) -> std::io::Result<()> {
+            *self.0.lock().unwrap() = None;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_keeps_the_refresh_token() {
+        let (token_url, _) = token_server();
+        let store = Arc::new(MemoryStore::default());
+        let mut client = client_refreshing_from(token_url);
+        client.set_token_store(Arc::clone(&store));
+
+        client.request_refresh_token().await.unwrap();
+
+        assert_eq!(&*client.access_token(), "second");
+        assert_eq!(client.refresh_token().as_deref(), Some("refresh"));
+
+        let saved = store.load().unwrap().unwrap();
+        assert_eq!(saved.refresh_token.unwrap().secret(), "refresh");
+    }
+
+    /// Fails to clear the saved token.
+    #[derive(Debug)]
+    struct FailingStore;
+
+    impl TokenStore for FailingStore {
+        fn load(&self) -> std::io::Result<Option<Token>> {
+            Ok(None)
+        }
+
+        fn save(&self, _token: &Token) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        fn clear(&self) -> std::io::Result<()> {
+            Err(std::io::ErrorKind::PermissionDenied.into())
+        }
+    }
+
+    #[tokio::test]
+    async fn logging_out_when_clearing_fails() {
+        let mut client = client();
+        client.set_token_store(FailingStore);
+
+        let err = client.logout().await.unwrap_err();
+
+        assert!(matches!(err, Error::Io(_)));
+        assert_eq!(&*client.access_token(), "first");
+    }
+
+    #[tokio::test]
+    async fn logging_out() {
+        let cleared = Arc::default();
+        let mut client = client();
+        client.set_token_store(ClearedStore(Arc::clone(&cleared)));
+        let clone = client.clone();
+
+        let logged_out = client.logout().await.unwrap();
+
+        assert!(cleared.load(std::sync::atomic::Ordering::SeqCst));
+        assert!(logged_out.token_store.is_some());
+        assert_eq!(&*clone.access_token(), "");
+
+        let err = clone.get_playback_state(None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::RefreshUnavailable | Error::ExpiredToken
+        ));
+    }
+
+    #[test]
+    fn clones_share_the_token() {
+        let client = client();
+        let clone = client.clone();
+
+        clone.set_token(token("second"));
+
+        assert_eq!(&*client.access_token(), "second");
+        assert_eq!(client.refresh_token().as_deref(), Some("refresh"));
+        assert_eq!(
+            format!("{:?}", client.access_token()),
+            "SecretRef([redacted])"
+        );
+    }
+
+    #[tokio::test]
+    async fn no_content_means_nothing_is_playing() {
+        let mut client = client();
+        client.set_transport(StaticTransport {
+            status: 204,
+            body: "",
+        });
+
+        assert!(client.get_playback_state(None).await.unwrap().is_none());
+        assert!(client
+            .get_currently_playing_track(None)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn seeking_without_playback() {
+        let mut client = client();
+        client.set_transport(StaticTransport {
+            status: 204,
+            body: "",
+        });
+
+        let res = client.seek_relative(15_000, None, false).await;
+        assert!(matches!(res, Err(Error::NothingPlaying)));
+    }
+
+    #[test]
+    fn relative_positions() {
+        let duration = Duration::from_secs(200);
+
+        assert_eq!(
+            relative_position(60_000, duration, 15_000, false),
+            Some(75_000)
+        );
+        assert_eq!(
+            relative_position(60_000, duration, -15_000, false),
+            Some(45_000)
+        );
+        assert_eq!(relative_position(10_000, duration, -15_000, false), Some(0));
+        assert_eq!(
+            relative_position(190_000, duration, 15_000, false),
+            Some(200_000)
+        );
+        assert_eq!(relative_position(190_000, duration, 15_000, true), None);
+        assert_eq!(
+            relative_position(190_000, duration, 5_000, true),
+            Some(195_000)
+        );
+        assert_eq!(
+            relative_position(0, duration, i64::MAX, false),
+            Some(200_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn pausing_without_playback() {
+        let mut client = client();
+        client.set_transport(StaticTransport {
+            status: 204,
+            body: "",
+        });
+
+        assert!(!client.ensure_paused(None).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn pausing_another_device() {
+        let urls = Arc::default();
+        let mut client = client();
+        client.set_transport(RecordingTransport {
+            body: include_str!("../tests/fixtures/playback_state.json"),
+            urls: Arc::clone(&urls),
+        });
+
+        assert!(!client.ensure_paused(Some("other")).await.unwrap());
+        assert_eq!(urls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn new_releases_pagination() {
+        let urls = Arc::default();
+        let mut client = client();
+        client.set_transport(RecordingTransport {
+            body: include_str!("../tests/fixtures/new_releases.json"),
+            urls: Arc::clone(&urls),
+        });
+
+        let page = client
+            .new_releases()
+            .country("SE")
+            .limit(1)
+            .offset(1)
+            .get()
+            .await
+            .unwrap();
+
+        assert_eq!((page.offset, page.limit, page.total), (1, 1, 100));
+        assert_eq!(page.items[0].name, "Global Warming");
+
+        let next = page.get_next(&client).await.unwrap().unwrap();
+        assert_eq!(next.items.len(), 1);
+
+        assert_eq!(
+            *urls.lock().unwrap(),
+            [
+                "https://api.spotify.com/v1/browse/new-releases?country=SE&limit=1&offset=1",
+                "https://api.spotify.com/v1/browse/new-releases?country=SE&offset=2&limit=1",
+            ]
+        );
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn secrets_are_wiped() {
+        let mut secret = crate::auth::Secret::new("access-token");
+        let (ptr, len) = (secret.secret().as_ptr(), secret.secret().len());
+
+        secret.wipe();
+
+        // The buffer is still owned by the (now empty) string, so it can be read.
+        let bytes = unsafe { std::slice::from_raw_parts(ptr, len) };
+        assert!(bytes.iter().all(|&b| b == 0));
+        assert_eq!(secret.secret(), "");
+        assert_eq!(format!("{secret:?}"), "Secret([redacted])");
+    }
+
+    #[tokio::test]
+    async fn streaming_playlists() {
+        use futures_util::TryStreamExt;
+
+        let urls = Arc::default();
+        let mut client = client();
+        client.set_transport(RecordingTransport {
+            body: include_str!("../tests/fixtures/current_user_playlists.json"),
+            urls: Arc::clone(&urls),
+        });
+
+        let stream = client.current_user_playlists().limit(2).stream();
+        assert!(urls.lock().unwrap().is_empty());
+
+        let playlists: Vec<_> = stream.try_collect().await.unwrap();
+        assert_eq!(playlists.len(), 2);
+        assert_eq!(urls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn library_ids_are_chunked() {
+        let counts = Arc::default();
+        let mut client = client();
+        client.set_transport(IdCountTransport(Arc::clone(&counts)));
+
+        client
+            .save_tracks((0..51).map(|i| i.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(*counts.lock().unwrap(), [50, 1]);
+
+        counts.lock().unwrap().clear();
+        client
+            .save_albums((0..21).map(|i| i.to_string()))
+            .await
+            .unwrap();
+        assert_eq!(*counts.lock().unwrap(), [20, 1]);
+    }
+
+    #[tokio::test]
+    async fn library_checks_are_chunked() {
+        let counts = Arc::default();
+        let mut client = client();
+        client.set_transport(IdCountTransport(Arc::clone(&counts)));
+
+        let ids: Vec<_> = (0..21).map(|i| i.to_string()).collect();
+        let saved = client.set_albums_saved(&ids, true).await.unwrap();
+        assert_eq!(saved.len(), 21);
+        assert_eq!(*counts.lock().unwrap(), [20, 1, 20, 1]);
+
+        counts.lock().unwrap().clear();
+        let ids: Vec<_> = (0..51).map(|i| i.to_string()).collect();
+        let followed = client.check_if_user_follows_artists(&ids).await.unwrap();
+        assert_eq!(followed.len(), 51);
+        assert_eq!(*counts.lock().unwrap(), [50, 1]);
+    }
+
+    #[tokio::test]
+    async fn premium_is_cached() {
+        let urls = Arc::default();
+        let mut client = client();
+        client.set_transport(RecordingTransport {
+            body: include_str!("../tests/fixtures/current_user.json"),
+            urls: Arc::clone(&urls),
+        });
+
+        assert_eq!(client.is_premium().await.unwrap(), Some(true));
+        assert_eq!(client.is_premium().await.unwrap(), Some(true));
+        client.current_user_id().await.unwrap();
+
+        assert_eq!(urls.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn unknown_product_is_not_cached() {
+        let urls = Arc::default();
+        let mut client = client();
+        client.set_transport(RecordingTransport {
+            body: r#"{
+                "external_urls": { "spotify": "https://open.spotify.com/user/listener" },
+                "followers": { "href": null, "total": 0 },
+                "href": "https://api.spotify.com/v1/users/listener",
+                "id": "listener",
+                "images": [],
+                "type": "user",
+                "uri": "spotify:user:listener"
+            }"#,
+            urls: Arc::clone(&urls),
+        });
+
+        assert_eq!(client.is_premium().await.unwrap(), None);
+        assert_eq!(client.is_premium().await.unwrap(), None);
+
+        assert_eq!(urls.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn episode_playback_state() {
+        let urls = Arc::default();
+        let mut client = client();
+        client.set_transport(RecordingTransport {
+            body: include_str!("../tests/fixtures/playback_state_episode.json"),
+            urls: Arc::clone(&urls),
+        });
+
+        let state = client
+            .get_playback_state(Some("SE"))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(state.item().unwrap().as_episode().is_some());
+        assert_eq!(
+            *urls.lock().unwrap(),
+            ["https://api.spotify.com/v1/me/player?additional_types=track%2Cepisode&market=SE"]
+        );
+    }
+
+    #[tokio::test]
+    async fn episode_playback_stream() {
+        use futures_util::StreamExt;
+
+        let urls = Arc::default();
+        let mut client = client();
+        client.set_transport(RecordingTransport {
+            body: include_str!("../tests/fixtures/playback_state_episode.json"),
+            urls: Arc::clone(&urls),
+        });
+
+        let stream = client.playback_stream(Duration::from_secs(1), true);
+        let state = std::pin::pin!(stream)
+            .next()
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert!(state.item().unwrap().as_episode().is_some());
+        assert_eq!(
+            *urls.lock().unwrap(),
+            ["https://api.spotify.com/v1/me/player?additional_types=track%2Cepisode"]
+        );
+    }
+
+    #[tokio::test]
+    async fn html_error_body() {
+        let mut client = client();
+        client.set_transport(StaticTransport {
+            status: 502,
+            body: "<html><body>Bad Gateway</body></html>",
+        });
+
+        let err = client.get_playback_state(None).await.unwrap_err();
+        assert!(matches!(err, Error::NonJsonError { status: 502, .. }));
+    }
+
+    #[tokio::test]
+    async fn chapter_html_server_error() {
+        let mut client = client();
+        client.set_transport(StaticTransport {
+            status: 502,
+            body: "<html><body>Bad Gateway</body></html>",
+        });
+
+        let err = client.chapter("id").get().await.unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UpstreamServerError {
+                status: 502,
+                message: None
+            }
+        ));
+    }
+
+    /// Serves refreshed tokens on a local port, returning its URL and the number of requests served.
+    ///
+    /// Like many of Spotify's responses, the refreshed tokens don't include a new refresh token.
+    fn token_server() -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/api/token", listener.local_addr().unwrap());
+        let served = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let count = served.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut reader = BufReader::new(stream.unwrap());
+                let mut length = 0;
+
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    } else if line.trim().is_empty() {
+                        break;
+                    }
+                }
+
+                reader.read_exact(&mut vec![0; length]).unwrap();
+                count.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
+                let body = r#"{"access_token":"second","token_type":"Bearer","expires_in":3600}"#;
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        (url, served)
+    }
+
+    /// A client whose token is refreshed from the given URL (see [`token_server`]).
+    fn client_refreshing_from(token_url: String) -> Client<Token, AuthCodeFlow, NoVerifier> {
+        let mut client = client();
+        client.oauth = OAuthClient::new(
+            ClientId::new("id".to_owned()),
+            None,
+            oauth2::AuthUrl::new("https://example.com/authorize".to_owned()).unwrap(),
+            Some(oauth2::TokenUrl::new(token_url).unwrap()),
+        );
+        client
+    }
+
+    /// Rejects requests as unauthorised unless they use the refreshed (`second`) access token.
+    #[derive(Debug)]
+    struct RefreshedTokenTransport;
+
+    impl HttpTransport for RefreshedTokenTransport {
+        fn send(&self, request: reqwest::Request) -> TransportFuture<'_> {
+            let refreshed = request
+                .headers()
+                .get(reqwest::header::AUTHORIZATION)
+                .is_some_and(|auth| auth == "Bearer second");
+
+            let res = if refreshed {
+                http::Response::builder().status(204).body("")
+            } else {
+                http::Response::builder()
+                    .status(401)
+                    .body(r#"{"error":{"status":401,"message":"The access token expired"}}"#)
+            };
+
+            Box::pin(async move { Ok(res.unwrap().into()) })
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_unauthorised_requests() {
+        let (token_url, refreshes) = token_server();
+        let mut client = client_refreshing_from(token_url);
+        client.set_transport(RefreshedTokenTransport);
+        let client = Arc::new(client);
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.get_playback_state(None).await })
+            })
+            .collect();
+
+        for task in tasks {
+            assert!(task.await.unwrap().unwrap().is_none());
+        }
+
+        assert_eq!(refreshes.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn other_unauthorised_errors_are_not_retried() {
+        let (token_url, refreshes) = token_server();
+        let mut client = client_refreshing_from(token_url);
+        client.set_transport(StaticTransport {
+            status: 401,
+            body: r#"{"error":{"status":401,"message":"This request requires user authentication."}}"#,
+        });
+
+        let err = client.get_playback_state(None).await.unwrap_err();
+
+        assert!(matches!(err, Error::Api { status: 401, .. }));
+        assert_eq!(refreshes.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn shared_between_tasks() {
+        let (token_url, refreshes) = token_server();
+        let mut client = client_refreshing_from(token_url);
+        client.set_transport(StaticTransport {
+            status: 204,
+            body: "",
+        });
+        client.auth.write().unwrap().expires_at = chrono::Utc::now();
+        let client = Arc::new(client);
+
+        let tasks: Vec<_> = (0..4)
+            .map(|_| {
+                let client = client.clone();
+                tokio::spawn(async move { client.get_playback_state(None).await })
+            })
+            .collect();
+
+        for task in tasks {
+            assert!(task.await.unwrap().unwrap().is_none());
+        }
+
+        assert_eq!(refreshes.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(&*client.access_token(), "second");
+    }
+}
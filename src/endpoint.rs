@@ -31,7 +31,7 @@ impl<T: Endpoint> PrivateEndpoint for T {}
 
 /// Builder for methods that get information from the API.
 pub struct Builder<'s, F: AuthFlow, V: Verifier, E: Endpoint> {
-    pub(crate) spotify: &'s mut Client<Token, F, V>,
+    pub(crate) spotify: &'s Client<Token, F, V>,
     pub(crate) endpoint: E,
 }
 
@@ -52,3 +52,8 @@ impl<const MIN: u32, const MAX: u32> Serialize for Limit<MIN, MAX> {
         serializer.serialize_u32(self.0)
     }
 }
+
+/// Split a comma-separated list of IDs (as sent in the query) back into the individual IDs.
+pub(crate) fn split_ids(ids: &str) -> Vec<String> {
+    ids.split(',').map(ToOwned::to_owned).collect()
+}
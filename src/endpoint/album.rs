@@ -1,3 +1,4 @@
+use futures_util::Stream;
 use serde::Serialize;
 
 use crate::{
@@ -5,6 +6,7 @@ use crate::{
     error::Result,
     model::{
         album::{Album, Albums, PagedAlbums, SavedAlbum, SimplifiedAlbum},
+        page_stream,
         track::SimplifiedTrack,
         Page,
     },
@@ -74,6 +76,10 @@ pub struct AlbumTracksEndpoint {
 
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AlbumTracksEndpoint> {
     #[doc = include_str!("../docs/market.md")]
+    ///
+    /// Supplying a market also enables [track relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking),
+    /// in which case the tracks' `is_playable` and `linked_from` fields are set. Without a market, the canonical
+    /// tracks of the album are returned as they are.
     pub fn market(mut self, market: impl Into<String>) -> Self {
         self.endpoint.market = Some(market.into());
         self
@@ -109,7 +115,7 @@ pub struct SavedAlbumsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedAlbumsEndpoint> {
+impl<'s, F: AuthFlow, V: Verifier> Builder<'s, F, V, SavedAlbumsEndpoint> {
     #[doc = include_str!("../docs/market.md")]
     pub fn market(mut self, market: impl Into<String>) -> Self {
         self.endpoint.market = Some(market.into());
@@ -134,6 +140,13 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedAlbumsEndpoint> {
             .get("/me/albums".to_owned(), self.endpoint)
             .await
     }
+
+    /// Stream all of the user's saved albums, requesting the pages one by one as the items are consumed,
+    /// instead of holding all of them in memory.
+    pub fn stream(self) -> impl Stream<Item = Result<SavedAlbum>> + 's {
+        let spotify = self.spotify;
+        page_stream(self.get(), spotify)
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
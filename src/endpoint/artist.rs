@@ -34,12 +34,19 @@ impl<'a, F: AuthFlow, V: Verifier> Builder<'a, F, V, ArtistEndpoint> {
         }
     }
 
-    pub fn top_tracks(self) -> Builder<'a, F, V, ArtistTopTracksEndpoint> {
+    /// Get the artist's top tracks in the given market, which Spotify requires for this endpoint.
+    ///
+    /// The market is an [ISO 3166-1 alpha-2](https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2) country code
+    /// (a [`Country`](crate::model::market::Country) can be passed as well).
+    pub fn top_tracks(
+        self,
+        market: impl Into<String>,
+    ) -> Builder<'a, F, V, ArtistTopTracksEndpoint> {
         Builder {
             spotify: self.spotify,
             endpoint: ArtistTopTracksEndpoint {
                 id: self.endpoint.id,
-                market: None,
+                market: market.into(),
             },
         }
     }
@@ -76,7 +83,11 @@ pub struct ArtistAlbumsEndpoint {
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistAlbumsEndpoint> {
     /// Sets the album types to be returned. If not supplied all album types will be returned.
     pub fn include_groups(mut self, include_groups: &[AlbumGroup]) -> Self {
-        self.endpoint.include_groups = Some(query_list(include_groups));
+        self.endpoint.include_groups = Some(query_list(
+            include_groups
+                .iter()
+                .filter(|group| **group != AlbumGroup::Unknown),
+        ));
         self
     }
 
@@ -113,16 +124,10 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistAlbumsEndpoint> {
 pub struct ArtistTopTracksEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
-    pub(crate) market: Option<String>,
+    pub(crate) market: String,
 }
 
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ArtistTopTracksEndpoint> {
-    #[doc = include_str!("../docs/market.md")]
-    pub fn market(mut self, market: impl Into<String>) -> Self {
-        self.endpoint.market = Some(market.into());
-        self
-    }
-
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Vec<Track>> {
         self.spotify
@@ -2,7 +2,7 @@ use serde::Serialize;
 
 use crate::{
     auth::{AuthFlow, Verifier},
-    error::Result,
+    error::{Error, Result},
     model::{
         audiobook::{
             Audiobook, Audiobooks, Chapter, Chapters, SimplifiedAudiobook, SimplifiedChapter,
@@ -11,7 +11,7 @@ use crate::{
     },
 };
 
-use super::{Builder, Endpoint, Limit};
+use super::{split_ids, Builder, Endpoint, Limit};
 
 impl Endpoint for AudiobookEndpoint {}
 impl Endpoint for AudiobooksEndpoint {}
@@ -155,11 +155,15 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ChapterEndpoint> {
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
+    /// Send the request.
+    ///
+    /// Spotify is known to return server errors for this endpoint,
+    /// which are returned as [`Error::UpstreamServerError`].
     pub async fn get(self) -> Result<Chapter> {
         self.spotify
             .get(format!("/chapters/{}", self.endpoint.id), self.endpoint)
             .await
+            .map_err(Error::upstream)
     }
 }
 
@@ -176,11 +180,22 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ChaptersEndpoint> {
         self
     }
 
-    #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self) -> Result<Vec<Chapter>> {
+    /// Send the request. Chapters that Spotify returns null for are `None`.
+    ///
+    /// Spotify is known to return server errors for this endpoint,
+    /// which are returned as [`Error::UpstreamServerError`].
+    pub async fn get(self) -> Result<Vec<Option<Chapter>>> {
         self.spotify
             .get("/chapters/".to_owned(), self.endpoint)
             .await
             .map(|c: Chapters| c.chapters)
+            .map_err(Error::upstream)
+    }
+
+    /// Send the request, pairing each requested ID with its chapter
+    /// (which is `None` if Spotify returned null for it).
+    pub async fn get_with_ids(self) -> Result<Vec<(String, Option<Chapter>)>> {
+        let ids = split_ids(&self.endpoint.ids);
+        self.get().await.map(|c| ids.into_iter().zip(c).collect())
     }
 }
@@ -2,17 +2,16 @@ use std::marker::PhantomData;
 
 use reqwest::Method;
 use serde::Serialize;
-use serde_json::{json, Value};
 
 use crate::{
     auth::{AuthFlow, Verifier},
     client::Body,
-    error::Result,
-    model::{player::PlayHistory, CursorPage},
+    error::{Error, Result},
+    model::{player::PlayHistory, uri::TrackUri, CursorPage},
     Nil,
 };
 
-use super::{Builder, Endpoint};
+use super::{Builder, Endpoint, Limit};
 
 impl Endpoint for TransferPlaybackEndpoint {}
 impl Endpoint for StartPlaybackEndpoint {}
@@ -23,7 +22,7 @@ impl Endpoint for ToggleShuffleEndpoint {}
 impl<T: TimestampMarker> Endpoint for RecentlyPlayedTracksEndpoint<T> {}
 impl Endpoint for AddItemToQueueEndpoint {}
 
-#[derive(Clone, Copy, Debug, Default, Serialize)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RepeatMode {
     Track,
@@ -58,13 +57,15 @@ pub struct Unspecified;
 
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct TransferPlaybackEndpoint {
+    // Spotify expects a list, but only supports transferring to a single device.
     pub(crate) device_ids: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) play: Option<bool>,
 }
 
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, TransferPlaybackEndpoint> {
     /// If `true`, ensure playback happens on the new device.
-    /// Otherwise, keep the current playback state.
+    /// If `false` or not set, keep the current playback state (i.e. playing or paused).
     pub fn play(mut self, play: bool) -> Self {
         self.endpoint.play = Some(play);
         self
@@ -78,13 +79,47 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, TransferPlaybackEndpoint> {
     }
 }
 
+/// Where in a context (an album or playlist) playback should start.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaybackOffset {
+    /// The (zero-based) index of the item in the context.
+    Position(u32),
+    /// The *URI* of the item in the context.
+    Uri(String),
+}
+
+impl From<u32> for PlaybackOffset {
+    fn from(value: u32) -> Self {
+        Self::Position(value)
+    }
+}
+
+impl From<String> for PlaybackOffset {
+    fn from(value: String) -> Self {
+        Self::Uri(value)
+    }
+}
+
+impl From<&str> for PlaybackOffset {
+    fn from(value: &str) -> Self {
+        Self::Uri(value.to_owned())
+    }
+}
+
+impl From<TrackUri> for PlaybackOffset {
+    fn from(value: TrackUri) -> Self {
+        Self::Uri(value.into())
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct StartPlaybackEndpoint {
     #[serde(skip)]
     pub(crate) device_id: Option<String>,
     pub(crate) context_uri: Option<String>,
     pub(crate) uris: Option<Vec<String>>,
-    pub(crate) offset: Option<Value>,
+    pub(crate) offset: Option<PlaybackOffset>,
     pub(crate) position_ms: Option<u32>,
 }
 
@@ -96,20 +131,39 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, StartPlaybackEndpoint> {
     }
 
     /// The *URI* of the context to play. Valid contexts are albums, artists and playlists.
+    ///
+    /// [`ContextUri`](crate::model::uri::ContextUri) can be used to create it from an ID.
     pub fn context_uri(mut self, context_uri: impl Into<String>) -> Self {
         self.endpoint.context_uri = Some(context_uri.into());
         self
     }
 
     /// The *URI*s of the tracks to play.
-    pub fn uris(mut self, uris: &[&str]) -> Self {
-        self.endpoint.uris = Some(uris.iter().map(ToString::to_string).collect());
+    pub fn uris(mut self, uris: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.endpoint.uris = Some(uris.into_iter().map(|u| u.as_ref().to_owned()).collect());
+        self
+    }
+
+    /// Where in the context playback should start, either as the index or the *URI* of an item.
+    ///
+    /// This can only be used together with [`context_uri`](Self::context_uri).
+    pub fn offset(mut self, offset: impl Into<PlaybackOffset>) -> Self {
+        self.endpoint.offset = Some(offset.into());
         self
     }
 
-    #[doc = include_str!("../docs/offset.md")]
-    pub fn offset(mut self, offset: u32) -> Self {
-        self.endpoint.offset = Some(json!({ "position": offset }));
+    /// Play the context (an album or playlist) starting at the given item,
+    /// either as its index or its *URI*.
+    ///
+    /// This replaces any *URI*s previously set with [`uris`](Self::uris).
+    pub fn play_context_at(
+        mut self,
+        context_uri: impl Into<String>,
+        offset: impl Into<PlaybackOffset>,
+    ) -> Self {
+        self.endpoint.context_uri = Some(context_uri.into());
+        self.endpoint.uris = None;
+        self.endpoint.offset = Some(offset.into());
         self
     }
 
@@ -121,6 +175,10 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, StartPlaybackEndpoint> {
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn send(self) -> Result<Nil> {
+        if self.endpoint.offset.is_some() && self.endpoint.context_uri.is_none() {
+            return Err(Error::OffsetWithoutContext);
+        }
+
         let device_id = self.endpoint.device_id.as_deref().unwrap_or("");
 
         self.spotify
@@ -236,25 +294,56 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, ToggleShuffleEndpoint> {
     }
 }
 
+/// Only one of `after` and `before` can be set, which is enforced at compile time:
+///
+/// ```compile_fail
+/// # use spotify_rs::{auth::{NoVerifier, Token}, client::Client, AuthCodeFlow};
+/// # fn f(spotify: &Client<Token, AuthCodeFlow, NoVerifier>) {
+/// spotify.recently_played_tracks().after(1).before(2);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # use spotify_rs::{auth::{NoVerifier, Token}, client::Client, AuthCodeFlow};
+/// # fn f(spotify: &Client<Token, AuthCodeFlow, NoVerifier>) {
+/// spotify.recently_played_tracks().before(2).after(1);
+/// # }
+/// ```
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct RecentlyPlayedTracksEndpoint<T: TimestampMarker = Unspecified> {
-    pub(crate) limit: Option<u32>,
+    pub(crate) limit: Option<Limit>,
     pub(crate) after: Option<u64>,
     pub(crate) before: Option<u64>,
+    #[serde(skip)]
     marker: PhantomData<T>,
 }
 
+impl RecentlyPlayedTracksEndpoint<Unspecified> {
+    fn after(self, after: u64) -> RecentlyPlayedTracksEndpoint<After> {
+        RecentlyPlayedTracksEndpoint {
+            limit: self.limit,
+            after: Some(after),
+            before: None,
+            marker: PhantomData,
+        }
+    }
+
+    fn before(self, before: u64) -> RecentlyPlayedTracksEndpoint<Before> {
+        RecentlyPlayedTracksEndpoint {
+            limit: self.limit,
+            after: None,
+            before: Some(before),
+            marker: PhantomData,
+        }
+    }
+}
+
 impl<'a, F: AuthFlow, V: Verifier> Builder<'a, F, V, RecentlyPlayedTracksEndpoint<Unspecified>> {
     /// A Unix timestamp in miliseconds. Returns all items after (but not including) this cursor position.
     pub fn after(self, after: u64) -> Builder<'a, F, V, RecentlyPlayedTracksEndpoint<After>> {
         Builder {
             spotify: self.spotify,
-            endpoint: RecentlyPlayedTracksEndpoint {
-                limit: self.endpoint.limit,
-                after: Some(after),
-                before: self.endpoint.before,
-                marker: PhantomData,
-            },
+            endpoint: self.endpoint.after(after),
         }
     }
 
@@ -262,14 +351,20 @@ impl<'a, F: AuthFlow, V: Verifier> Builder<'a, F, V, RecentlyPlayedTracksEndpoin
     pub fn before(self, before: u64) -> Builder<'a, F, V, RecentlyPlayedTracksEndpoint<Before>> {
         Builder {
             spotify: self.spotify,
-            endpoint: RecentlyPlayedTracksEndpoint {
-                limit: self.endpoint.limit,
-                after: self.endpoint.after,
-                before: Some(before),
-                marker: PhantomData,
-            },
+            endpoint: self.endpoint.before(before),
         }
     }
+
+    /// Continue from a previously retrieved page, i.e. request the tracks played before it.
+    ///
+    /// Returns `None` if the page doesn't have a valid `before` cursor, meaning it's the last page.
+    pub fn continue_with(
+        self,
+        page: &CursorPage<PlayHistory>,
+    ) -> Option<Builder<'a, F, V, RecentlyPlayedTracksEndpoint<Before>>> {
+        let before = page.next_before()?.parse().ok()?;
+        Some(self.before(before))
+    }
 }
 
 impl<F: AuthFlow, V: Verifier, T: TimestampMarker>
@@ -277,7 +372,7 @@ impl<F: AuthFlow, V: Verifier, T: TimestampMarker>
 {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
-        self.endpoint.limit = Some(limit);
+        self.endpoint.limit = Some(Limit::new(limit));
         self
     }
 
@@ -314,3 +409,48 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AddItemToQueueEndpoint> {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn recently_played_keeps_limit_after_timestamp() {
+        let endpoint = RecentlyPlayedTracksEndpoint {
+            limit: Some(Limit::new(10)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(endpoint.clone().after(1)).unwrap(),
+            json!({ "limit": 10, "after": 1, "before": null })
+        );
+        assert_eq!(
+            serde_json::to_value(endpoint.before(2)).unwrap(),
+            json!({ "limit": 10, "after": null, "before": 2 })
+        );
+    }
+
+    #[test]
+    fn transfer_playback_omits_unset_play() {
+        let endpoint = TransferPlaybackEndpoint {
+            device_ids: vec!["device".to_owned()],
+            play: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&endpoint).unwrap(),
+            json!({ "device_ids": ["device"] })
+        );
+        assert_eq!(
+            serde_json::to_value(TransferPlaybackEndpoint {
+                play: Some(false),
+                ..endpoint
+            })
+            .unwrap(),
+            json!({ "device_ids": ["device"], "play": false })
+        );
+    }
+}
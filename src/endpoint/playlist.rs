@@ -1,17 +1,21 @@
+#[cfg(feature = "deprecated-endpoints")]
 use chrono::{DateTime, Utc};
+use futures_util::Stream;
 use serde::Serialize;
 use serde_json::Value;
 
+#[cfg(feature = "deprecated-endpoints")]
+use crate::model::playlist::{FeaturedPlaylists, Playlists};
 use crate::{
     auth::{AuthFlow, Verifier},
     error::Result,
     model::{
-        playlist::{
-            FeaturedPlaylists, Playlist, PlaylistTrack, Playlists, SimplifiedPlaylist, SnapshotId,
-        },
-        Page,
+        page_stream,
+        playlist::{Playlist, PlaylistTrack, SimplifiedPlaylist, Snapshot, SnapshotId},
+        track::Track,
+        Page, PlayableItem,
     },
-    Nil,
+    query_list, Nil,
 };
 
 use super::{Builder, Endpoint, Limit, PrivateEndpoint};
@@ -25,7 +29,9 @@ impl Endpoint for RemovePlaylistItemsEndpoint {}
 impl Endpoint for CurrentUserPlaylistsEndpoint {}
 impl Endpoint for UserPlaylistsEndpoint {}
 impl Endpoint for CreatePlaylistEndpoint<'_> {}
+#[cfg(feature = "deprecated-endpoints")]
 impl Endpoint for FeaturedPlaylistsEndpoint {}
+#[cfg(feature = "deprecated-endpoints")]
 impl Endpoint for CategoryPlaylistsEndpoint {}
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -33,6 +39,8 @@ pub struct PlaylistEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    pub(crate) fields: Option<String>,
+    pub(crate) additional_types: Option<String>,
 }
 
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistEndpoint> {
@@ -42,12 +50,40 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistEndpoint> {
         self
     }
 
+    /// Filters the response to only include the specified fields, for example
+    /// `name,tracks.items(track(name,href))`.
+    ///
+    /// Filtering changes the shape of the response, so it most likely won't deserialize
+    /// into a [`Playlist`]. Use [`get_raw`](Self::get_raw) to get the filtered JSON instead.
+    pub fn fields(mut self, fields: impl Into<String>) -> Self {
+        self.endpoint.fields = Some(fields.into());
+        self
+    }
+
+    #[doc = include_str!("../docs/additional_types.md")]
+    pub fn additional_types(
+        mut self,
+        additional_types: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        self.endpoint.additional_types = Some(query_list(additional_types));
+        self
+    }
+
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Playlist> {
         self.spotify
             .get(format!("/playlists/{}", self.endpoint.id), self.endpoint)
             .await
     }
+
+    /// Sends the actual request, returning the raw JSON response.
+    ///
+    /// This is useful when using [`fields`](Self::fields) to filter the response.
+    pub async fn get_raw(self) -> Result<Value> {
+        self.spotify
+            .get(format!("/playlists/{}", self.endpoint.id), self.endpoint)
+            .await
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -107,17 +143,29 @@ pub struct PlaylistItemsEndpoint {
     #[serde(skip)]
     pub(crate) id: String,
     pub(crate) market: Option<String>,
+    pub(crate) fields: Option<String>,
+    pub(crate) additional_types: Option<String>,
     pub(crate) limit: Option<Limit>,
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistItemsEndpoint> {
+impl<'s, F: AuthFlow, V: Verifier> Builder<'s, F, V, PlaylistItemsEndpoint> {
     #[doc = include_str!("../docs/market.md")]
     pub fn market(mut self, market: impl Into<String>) -> Self {
         self.endpoint.market = Some(market.into());
         self
     }
 
+    /// Filters the response to only include the specified fields, for example
+    /// `items(added_by.id,track(name,href))`.
+    ///
+    /// Filtering changes the shape of the response, so it most likely won't deserialize
+    /// into a [`Page`] of [`PlaylistTrack`]s. Use [`get_raw`](Self::get_raw) to get the filtered JSON instead.
+    pub fn fields(mut self, fields: impl Into<String>) -> Self {
+        self.endpoint.fields = Some(fields.into());
+        self
+    }
+
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -130,6 +178,15 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistItemsEndpoint> {
         self
     }
 
+    #[doc = include_str!("../docs/additional_types.md")]
+    pub fn additional_types(
+        mut self,
+        additional_types: impl IntoIterator<Item = impl AsRef<str>>,
+    ) -> Self {
+        self.endpoint.additional_types = Some(query_list(additional_types));
+        self
+    }
+
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Page<PlaylistTrack>> {
         self.spotify
@@ -139,6 +196,49 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, PlaylistItemsEndpoint> {
             )
             .await
     }
+
+    /// Stream all of the playlist's items, requesting the pages one by one as the items are consumed,
+    /// instead of holding all of them in memory.
+    pub fn stream(self) -> impl Stream<Item = Result<PlaylistTrack>> + 's {
+        let spotify = self.spotify;
+        page_stream(self.get(), spotify)
+    }
+
+    /// Sends the actual request, returning the raw JSON response.
+    ///
+    /// This is useful when using [`fields`](Self::fields) to filter the response.
+    pub async fn get_raw(self) -> Result<Value> {
+        self.spotify
+            .get(
+                format!("/playlists/{}/tracks", self.endpoint.id),
+                self.endpoint,
+            )
+            .await
+    }
+
+    /// Get all the tracks in the playlist, leaving out episodes, local files and unavailable items.
+    ///
+    /// Spotify can't filter the items itself, so all of the playlist's items are still requested,
+    /// page by page, and filtered afterwards. This won't work with [`fields`](Self::fields).
+    pub async fn tracks_only(self) -> Result<Vec<Track>> {
+        let page: Page<PlaylistTrack> = self
+            .spotify
+            .get(
+                format!("/playlists/{}/tracks", self.endpoint.id),
+                self.endpoint,
+            )
+            .await?;
+
+        let items = page.get_all(self.spotify).await?;
+
+        Ok(items
+            .into_iter()
+            .filter_map(|item| match item.track {
+                Some(PlayableItem::Track(track)) => Some(track),
+                _ => None,
+            })
+            .collect())
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -152,7 +252,7 @@ pub struct UpdatePlaylistItemsEndpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) range_length: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) snapshot_id: Option<String>,
+    pub(crate) snapshot_id: Option<SnapshotId>,
 }
 
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UpdatePlaylistItemsEndpoint> {
@@ -175,20 +275,20 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UpdatePlaylistItemsEndpoint> {
     }
 
     /// The playlist's snapshot ID against which to make changes.
-    pub fn snapshot_id(mut self, snapshot_id: impl Into<String>) -> Self {
+    pub fn snapshot_id(mut self, snapshot_id: impl Into<SnapshotId>) -> Self {
         self.endpoint.snapshot_id = Some(snapshot_id.into());
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn send(self) -> Result<String> {
+    pub async fn send(self) -> Result<SnapshotId> {
         self.spotify
             .put(
                 format!("/playlists/{}/tracks", self.endpoint.id),
                 self.endpoint.json(),
             )
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: Snapshot| i.snapshot_id)
     }
 }
 
@@ -209,14 +309,14 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, AddPlaylistItemsEndpoint> {
     }
 
     #[doc = include_str!("../docs/send.md")]
-    pub async fn send(self) -> Result<String> {
+    pub async fn send(self) -> Result<SnapshotId> {
         self.spotify
             .post(
                 format!("/playlists/{}/tracks", self.endpoint.id),
                 self.endpoint.json(),
             )
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: Snapshot| i.snapshot_id)
     }
 }
 
@@ -226,26 +326,25 @@ pub struct RemovePlaylistItemsEndpoint {
     pub(crate) id: String,
     pub(crate) tracks: Vec<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) snapshot_id: Option<String>,
+    pub(crate) snapshot_id: Option<SnapshotId>,
 }
 
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RemovePlaylistItemsEndpoint> {
     /// The playlist's snapshot ID against which to make changes.
-    pub fn snapshot_id(mut self, snapshot_id: impl Into<String>) -> Self {
+    pub fn snapshot_id(mut self, snapshot_id: impl Into<SnapshotId>) -> Self {
         self.endpoint.snapshot_id = Some(snapshot_id.into());
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
-
-    pub async fn send(self) -> Result<String> {
+    pub async fn send(self) -> Result<SnapshotId> {
         self.spotify
             .delete(
                 format!("/playlists/{}/tracks", self.endpoint.id),
                 self.endpoint.json(),
             )
             .await
-            .map(|i: SnapshotId| i.snapshot_id)
+            .map(|i: Snapshot| i.snapshot_id)
     }
 }
 
@@ -255,7 +354,7 @@ pub struct CurrentUserPlaylistsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, CurrentUserPlaylistsEndpoint> {
+impl<'s, F: AuthFlow, V: Verifier> Builder<'s, F, V, CurrentUserPlaylistsEndpoint> {
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -274,6 +373,13 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, CurrentUserPlaylistsEndpoint> {
             .get("/me/playlists".to_owned(), self.endpoint)
             .await
     }
+
+    /// Stream all of the user's playlists, requesting the pages one by one as the items are consumed,
+    /// instead of holding all of them in memory.
+    pub fn stream(self) -> impl Stream<Item = Result<SimplifiedPlaylist>> + 's {
+        let spotify = self.spotify;
+        page_stream(self.get(), spotify)
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -373,6 +479,7 @@ impl<'a, F: AuthFlow, V: Verifier> Builder<'_, F, V, CreatePlaylistEndpoint<'a>>
     }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct FeaturedPlaylistsEndpoint {
     pub(crate) country: Option<String>,
@@ -382,6 +489,7 @@ pub struct FeaturedPlaylistsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
     #[doc = include_str!("../docs/country.md")]
     pub fn country(mut self, country: impl Into<String>) -> Self {
@@ -421,6 +529,7 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FeaturedPlaylistsEndpoint> {
     }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct CategoryPlaylistsEndpoint {
     #[serde(skip)]
@@ -430,6 +539,7 @@ pub struct CategoryPlaylistsEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, CategoryPlaylistsEndpoint> {
     #[doc = include_str!("../docs/country.md")]
     pub fn country(mut self, country: impl Into<String>) -> Self {
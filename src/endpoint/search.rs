@@ -22,7 +22,7 @@ pub struct SearchEndpoint {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) offset: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) include_external: Option<bool>,
+    pub(crate) include_external: Option<&'static str>,
 }
 
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SearchEndpoint> {
@@ -44,12 +44,12 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SearchEndpoint> {
         self
     }
 
-    /// If specified, it signals that the client can play externally hosted audio content,
-    /// and marks the content as playable in the response.
+    /// If `true`, it signals that the client can play externally hosted audio content,
+    /// and marks the content as playable in the response (`include_external=audio`).
     ///
     /// By default externally hosted audio content is marked as unplayable in the response.
-    pub fn include_external(mut self, include_external: bool) -> Self {
-        self.endpoint.include_external = Some(include_external);
+    pub fn include_external_audio(mut self, include_external_audio: bool) -> Self {
+        self.endpoint.include_external = include_external_audio.then_some("audio");
         self
     }
 
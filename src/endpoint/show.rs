@@ -12,7 +12,7 @@ use crate::{
     },
 };
 
-use super::{Builder, Endpoint, Limit};
+use super::{split_ids, Builder, Endpoint, Limit};
 
 impl Endpoint for ShowEndpoint {}
 impl Endpoint for ShowsEndpoint {}
@@ -169,13 +169,22 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, EpisodesEndpoint> {
         self
     }
 
+    // This doesn't flatten the result into a Vec<Episode> because the user might want to
+    // know that some of the episodes they want return null.
     #[doc = include_str!("../docs/send.md")]
-    pub async fn get(self) -> Result<Vec<Episode>> {
+    pub async fn get(self) -> Result<Vec<Option<Episode>>> {
         self.spotify
             .get("/episodes/".to_owned(), self.endpoint)
             .await
             .map(|e: Episodes| e.episodes)
     }
+
+    /// Send the request, pairing each requested ID with its episode
+    /// (which is `None` if Spotify returned null for it).
+    pub async fn get_with_ids(self) -> Result<Vec<(String, Option<Episode>)>> {
+        let ids = split_ids(&self.endpoint.ids);
+        self.get().await.map(|e| ids.into_iter().zip(e).collect())
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
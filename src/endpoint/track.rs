@@ -1,35 +1,48 @@
+#[cfg(feature = "deprecated-endpoints")]
 use std::{collections::HashMap, marker::PhantomData};
 
+use futures_util::Stream;
 use serde::Serialize;
+#[cfg(feature = "deprecated-endpoints")]
 use strum::IntoStaticStr;
 
 use crate::{
     auth::{AuthFlow, Verifier},
     error::Result,
     model::{
-        recommendation::Recommendations,
+        page_stream,
         track::{SavedTrack, Track, Tracks},
         Page,
     },
-    query_list,
 };
+#[cfg(feature = "deprecated-endpoints")]
+use crate::{error::Error, model::recommendation::Recommendations, query_list};
 
 use super::{Builder, Endpoint, Limit};
 
 impl Endpoint for TrackEndpoint {}
 impl Endpoint for TracksEndpoint {}
 impl Endpoint for SavedTracksEndpoint {}
+#[cfg(feature = "deprecated-endpoints")]
 impl<S: SeedType> Endpoint for RecommendationsEndpoint<S> {}
 
+#[cfg(feature = "deprecated-endpoints")]
 pub trait SeedType {}
+#[cfg(feature = "deprecated-endpoints")]
 impl SeedType for SeedArtists {}
+#[cfg(feature = "deprecated-endpoints")]
 impl SeedType for SeedGenres {}
+#[cfg(feature = "deprecated-endpoints")]
 impl SeedType for SeedTracks {}
 
+#[cfg(feature = "deprecated-endpoints")]
 pub enum SeedArtists {}
+#[cfg(feature = "deprecated-endpoints")]
 pub enum SeedGenres {}
+#[cfg(feature = "deprecated-endpoints")]
 pub enum SeedTracks {}
 
+#[cfg(feature = "deprecated-endpoints")]
 #[derive(Clone, Debug)]
 pub enum Seed<'a, T: AsRef<str>, S: SeedType> {
     Artists(&'a [T], PhantomData<S>),
@@ -37,71 +50,146 @@ pub enum Seed<'a, T: AsRef<str>, S: SeedType> {
     Tracks(&'a [T], PhantomData<S>),
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<'a, T: AsRef<str> + Clone> Seed<'a, T, SeedArtists> {
     pub fn artists(ids: &'a [T]) -> Self {
         Self::Artists(ids, PhantomData)
     }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<'a, T: AsRef<str> + Clone> Seed<'a, T, SeedGenres> {
     pub fn genres(genres: &'a [T]) -> Self {
         Self::Genres(genres, PhantomData)
     }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<'a, T: AsRef<str> + Clone> Seed<'a, T, SeedTracks> {
     pub fn tracks(ids: &'a [T]) -> Self {
         Self::Tracks(ids, PhantomData)
     }
 }
 
-#[derive(Clone, Copy, Debug, Serialize, IntoStaticStr)]
-#[serde(untagged)]
-#[serde(rename_all = "snake_case")]
+/// A tunable track attribute used for filtering and targeting recommendations.
+#[cfg(feature = "deprecated-endpoints")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoStaticStr)]
 #[strum(serialize_all = "snake_case")]
-pub enum Feature {
-    MinAcousticness(f32),
-    MaxAcousticness(f32),
-    TargetAcousticness(f32),
-    MinDanceability(f32),
-    MaxDanceability(f32),
-    TargetDanceability(f32),
-    MinDurationMs(u32),
-    MaxDurationMs(u32),
-    TargetDurationMs(u32),
-    MinEnergy(f32),
-    MaxEnergy(f32),
-    TargetEnergy(f32),
-    MinInstrumentalness(f32),
-    MaxInstrumentalness(f32),
-    TargetInstrumentalness(f32),
-    MinKey(u32),
-    MaxKey(u32),
-    TargetKey(u32),
-    MinLiveness(f32),
-    MaxLiveness(f32),
-    TargetLiveness(f32),
-    MinLoudness(f32),
-    MaxLoudness(f32),
-    TargetLoudness(f32),
-    MinMode(u32),
-    MaxMode(u32),
-    TargetMode(u32),
-    MinPopularity(u32),
-    MaxPopularity(u32),
-    TargetPopularity(u32),
-    MinSpeechiness(f32),
-    MaxSpeechiness(f32),
-    TargetSpeechiness(f32),
-    MinTempo(f32),
-    MaxTempo(f32),
-    TargetTempo(f32),
-    MinTimeSignature(u32),
-    MaxTimeSignature(u32),
-    TargetTimeSignature(u32),
-    MinValence(f32),
-    MaxValence(f32),
-    TargetValence(f32),
+pub enum FeatureKind {
+    Acousticness,
+    Danceability,
+    DurationMs,
+    Energy,
+    Instrumentalness,
+    Key,
+    Liveness,
+    Loudness,
+    Mode,
+    Popularity,
+    Speechiness,
+    Tempo,
+    TimeSignature,
+    Valence,
+}
+
+/// The value of a [`Feature`], which is an integer for attributes such as
+/// [`FeatureKind::Key`] and a float for attributes such as [`FeatureKind::Energy`].
+#[cfg(feature = "deprecated-endpoints")]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum FeatureValue {
+    Int(u32),
+    Float(f32),
+}
+
+#[cfg(feature = "deprecated-endpoints")]
+impl From<u32> for FeatureValue {
+    fn from(value: u32) -> Self {
+        Self::Int(value)
+    }
+}
+
+#[cfg(feature = "deprecated-endpoints")]
+impl From<f32> for FeatureValue {
+    fn from(value: f32) -> Self {
+        Self::Float(value)
+    }
+}
+
+/// A minimum, maximum and/or target value for a [`FeatureKind`].
+#[cfg(feature = "deprecated-endpoints")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Feature {
+    pub(crate) kind: FeatureKind,
+    pub(crate) min: Option<FeatureValue>,
+    pub(crate) max: Option<FeatureValue>,
+    pub(crate) target: Option<FeatureValue>,
+}
+
+#[cfg(feature = "deprecated-endpoints")]
+impl Feature {
+    fn new(kind: FeatureKind) -> Self {
+        Self {
+            kind,
+            min: None,
+            max: None,
+            target: None,
+        }
+    }
+
+    /// A hard floor on the value of the attribute.
+    pub fn min(kind: FeatureKind, value: impl Into<FeatureValue>) -> Self {
+        Self {
+            min: Some(value.into()),
+            ..Self::new(kind)
+        }
+    }
+
+    /// A hard ceiling on the value of the attribute.
+    pub fn max(kind: FeatureKind, value: impl Into<FeatureValue>) -> Self {
+        Self {
+            max: Some(value.into()),
+            ..Self::new(kind)
+        }
+    }
+
+    /// A target value for the attribute. Tracks with the attribute values nearest to
+    /// the target values will be preferred.
+    pub fn target(kind: FeatureKind, value: impl Into<FeatureValue>) -> Self {
+        Self {
+            target: Some(value.into()),
+            ..Self::new(kind)
+        }
+    }
+
+    /// Sets the minimum, maximum and target to the same value.
+    pub fn exact(kind: FeatureKind, value: impl Into<FeatureValue>) -> Self {
+        let value = Some(value.into());
+
+        Self {
+            kind,
+            min: value,
+            max: value,
+            target: value,
+        }
+    }
+
+    /// The attribute this feature applies to.
+    pub fn kind(&self) -> FeatureKind {
+        self.kind
+    }
+
+    fn params(&self) -> impl Iterator<Item = (String, FeatureValue)> + '_ {
+        let kind: &'static str = self.kind.into();
+
+        [
+            ("min", self.min),
+            ("max", self.max),
+            ("target", self.target),
+        ]
+        .into_iter()
+        .filter_map(move |(prefix, value)| value.map(|v| (format!("{prefix}_{kind}"), v)))
+    }
 }
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -154,7 +242,7 @@ pub struct SavedTracksEndpoint {
     pub(crate) offset: Option<u32>,
 }
 
-impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedTracksEndpoint> {
+impl<'s, F: AuthFlow, V: Verifier> Builder<'s, F, V, SavedTracksEndpoint> {
     #[doc = include_str!("../docs/market.md")]
     pub fn market(mut self, market: impl Into<String>) -> Self {
         self.endpoint.market = Some(market.into());
@@ -179,8 +267,16 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, SavedTracksEndpoint> {
             .get("/me/tracks".to_owned(), self.endpoint)
             .await
     }
+
+    /// Stream all of the user's saved tracks, requesting the pages one by one as the items are consumed,
+    /// instead of holding all of them in memory.
+    pub fn stream(self) -> impl Stream<Item = Result<SavedTrack>> + 's {
+        let spotify = self.spotify;
+        page_stream(self.get(), spotify)
+    }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct RecommendationsEndpoint<S: SeedType> {
     pub(crate) seed_artists: Option<String>,
@@ -189,55 +285,85 @@ pub struct RecommendationsEndpoint<S: SeedType> {
     pub(crate) limit: Option<Limit<1, 100>>,
     pub(crate) market: Option<String>,
     #[serde(flatten)]
-    pub(crate) features: Option<HashMap<&'static str, Feature>>,
+    pub(crate) features: Option<HashMap<String, FeatureValue>>,
     #[serde(skip)]
     pub(crate) marker: PhantomData<S>,
 }
 
+#[cfg(feature = "deprecated-endpoints")]
+impl<S: SeedType> RecommendationsEndpoint<S> {
+    /// The maximum number of seeds (artists, genres and tracks combined) Spotify accepts.
+    const MAX_SEEDS: usize = 5;
+
+    fn seed_count(&self) -> usize {
+        [&self.seed_artists, &self.seed_genres, &self.seed_tracks]
+            .into_iter()
+            .flatten()
+            .map(|seeds| seeds.split(',').filter(|s| !s.is_empty()).count())
+            .sum()
+    }
+
+    /// Checks the request against Spotify's limits before it's sent.
+    fn validate(&self) -> Result<()> {
+        let count = self.seed_count();
+
+        if count > Self::MAX_SEEDS {
+            return Err(Error::TooManySeeds { count });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "deprecated-endpoints")]
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedArtists>> {
     /// Up to 5 Spotify genre IDs used for seeding the recommendations.
-    pub fn seed_genres<T: AsRef<str>>(mut self, genres: &[T]) -> Self {
+    pub fn seed_genres(mut self, genres: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
         self.endpoint.seed_genres = Some(query_list(genres));
         self
     }
 
     /// Up to 5 Spotify track IDs used for seeding the recommendations.
-    pub fn seed_tracks<T: AsRef<str>>(mut self, track_ids: &[T]) -> Self {
+    pub fn seed_tracks(mut self, track_ids: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
         self.endpoint.seed_tracks = Some(query_list(track_ids));
         self
     }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedGenres>> {
     /// Up to 5 Spotify artist IDs used for seeding the recommendations.
-    pub fn seed_artists<T: AsRef<str>>(mut self, artist_ids: &[T]) -> Self {
-        self.endpoint.seed_genres = Some(query_list(artist_ids));
+    pub fn seed_artists(mut self, artist_ids: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.endpoint.seed_artists = Some(query_list(artist_ids));
         self
     }
 
     /// Up to 5 Spotify track IDs used for seeding the recommendations.
-    pub fn seed_tracks<T: AsRef<str>>(mut self, track_ids: &[T]) -> Self {
+    pub fn seed_tracks(mut self, track_ids: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
         self.endpoint.seed_tracks = Some(query_list(track_ids));
         self
     }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, RecommendationsEndpoint<SeedTracks>> {
     /// Up to 5 Spotify genre IDs used for seeding the recommendations.
-    pub fn seed_genres<T: AsRef<str>>(mut self, genres: &[T]) -> Self {
+    pub fn seed_genres(mut self, genres: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
         self.endpoint.seed_genres = Some(query_list(genres));
         self
     }
 
     /// Up to 5 Spotify artist IDs used for seeding the recommendations.
-    pub fn seed_artists<T: AsRef<str>>(mut self, artist_ids: &[T]) -> Self {
-        self.endpoint.seed_genres = Some(query_list(artist_ids));
+    pub fn seed_artists(mut self, artist_ids: impl IntoIterator<Item = impl AsRef<str>>) -> Self {
+        self.endpoint.seed_artists = Some(query_list(artist_ids));
         self
     }
 }
 
+#[cfg(feature = "deprecated-endpoints")]
 impl<F: AuthFlow, V: Verifier, S: SeedType> Builder<'_, F, V, RecommendationsEndpoint<S>> {
-    #[doc = include_str!("../docs/limit.md")]
+    /// The target size of the list of recommended tracks. Defaults to `20`.
+    /// Must be a value between `1 - 100`, values outside of that range are clamped.
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
         self
@@ -252,19 +378,93 @@ impl<F: AuthFlow, V: Verifier, S: SeedType> Builder<'_, F, V, RecommendationsEnd
     /// A list of [`Feature`]s. Read more about the available features
     /// [here](https://developer.spotify.com/documentation/web-api/reference/get-recommendations).
     pub fn features(mut self, features: &[Feature]) -> Self {
-        let features: HashMap<&'static str, Feature> = features
-            .iter()
-            .map(|f| (From::<Feature>::from(*f), *f))
-            .collect();
-        dbg!(&features);
+        let features = features.iter().flat_map(Feature::params).collect();
         self.endpoint.features = Some(features);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
     pub async fn get(self) -> Result<Recommendations> {
+        self.endpoint.validate()?;
+
         self.spotify
             .get("/recommendations".to_owned(), self.endpoint)
             .await
     }
 }
+
+#[cfg(all(test, feature = "deprecated-endpoints"))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn recommendations(artists: &[&str], genres: &[&str]) -> RecommendationsEndpoint<SeedArtists> {
+        RecommendationsEndpoint {
+            seed_artists: Some(query_list(artists)),
+            seed_genres: Some(query_list(genres)),
+            seed_tracks: None,
+            limit: None,
+            market: None,
+            features: None,
+            marker: PhantomData,
+        }
+    }
+
+    #[test]
+    fn five_seeds_are_allowed() {
+        let endpoint = recommendations(&["a", "b", "c"], &["rock", "pop"]);
+
+        assert_eq!(endpoint.seed_count(), 5);
+        assert!(endpoint.validate().is_ok());
+    }
+
+    #[test]
+    fn six_seeds_are_too_many() {
+        let endpoint = recommendations(&["a", "b", "c"], &["rock", "pop", "jazz"]);
+
+        assert!(matches!(
+            endpoint.validate(),
+            Err(Error::TooManySeeds { count: 6 })
+        ));
+    }
+
+    #[test]
+    fn features_serialize_to_spotify_keys() {
+        let mut endpoint = recommendations(&["a"], &[]);
+        endpoint.features = Some(
+            [
+                Feature::min(FeatureKind::Energy, 0.5),
+                Feature::max(FeatureKind::Tempo, 120.0),
+                Feature::target(FeatureKind::Mode, 1),
+                Feature::target(FeatureKind::DurationMs, 200_000),
+            ]
+            .iter()
+            .flat_map(Feature::params)
+            .collect(),
+        );
+
+        let query = serde_json::to_value(&endpoint).unwrap();
+
+        assert_eq!(query["min_energy"], json!(0.5));
+        assert_eq!(query["max_tempo"], json!(120.0));
+        assert_eq!(query["target_mode"], json!(1));
+        assert_eq!(query["target_duration_ms"], json!(200_000));
+    }
+
+    #[test]
+    fn exact_feature_sets_min_max_and_target() {
+        let mut endpoint = recommendations(&["a"], &[]);
+        endpoint.features = Some(
+            Feature::exact(FeatureKind::Popularity, 50)
+                .params()
+                .collect(),
+        );
+
+        let query = serde_json::to_value(&endpoint).unwrap();
+
+        assert_eq!(query["min_popularity"], json!(50));
+        assert_eq!(query["max_popularity"], json!(50));
+        assert_eq!(query["target_popularity"], json!(50));
+    }
+}
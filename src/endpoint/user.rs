@@ -1,3 +1,4 @@
+use reqwest::Url;
 use serde::Serialize;
 use serde_json::json;
 
@@ -43,19 +44,48 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, UserTopItemsEndpoint> {
     }
 
     #[doc = include_str!("../docs/offset.md")]
+    ///
+    /// The offset is relative to the ranking of the selected time range.
     pub fn offset(mut self, offset: u32) -> Self {
         self.endpoint.offset = Some(offset);
         self
     }
 
     #[doc = include_str!("../docs/send.md")]
+    ///
+    /// The pages after the returned one are requested with the same time range.
     pub async fn get(self) -> Result<Page<UserItem>> {
-        self.spotify
+        let time_range = self.endpoint.time_range.clone();
+        let mut page: Page<UserItem> = self
+            .spotify
             .get(format!("/me/top/{}", self.endpoint.r#type), self.endpoint)
-            .await
+            .await?;
+
+        if let Some(time_range) = time_range {
+            page.next = page.next.map(|next| with_time_range(next, &time_range));
+        }
+
+        Ok(page)
     }
 }
 
+/// Add the time range to a page URL if it's missing, as otherwise the next page
+/// would be from the default (medium term) ranking.
+fn with_time_range(url: String, time_range: &TimeRange) -> String {
+    let Ok(mut parsed) = Url::parse(&url) else {
+        return url;
+    };
+
+    if parsed.query_pairs().any(|(k, _)| k == "time_range") {
+        return url;
+    }
+
+    parsed
+        .query_pairs_mut()
+        .append_pair("time_range", time_range.as_ref());
+    parsed.into()
+}
+
 #[derive(Clone, Debug, Default, Serialize)]
 pub struct FollowPlaylistBuilder {
     #[serde(skip)]
@@ -97,6 +127,14 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FollowedArtistsBuilder> {
         self
     }
 
+    /// Continue from a previously retrieved page, i.e. request the artists after it.
+    ///
+    /// Returns `None` if the page doesn't have an `after` cursor, meaning it's the last page.
+    pub fn continue_with(self, page: &CursorPage<Artist>) -> Option<Self> {
+        let after = page.next_after()?.to_owned();
+        Some(self.after(after))
+    }
+
     #[doc = include_str!("../docs/limit.md")]
     pub fn limit(mut self, limit: u32) -> Self {
         self.endpoint.limit = Some(Limit::new(limit));
@@ -153,3 +191,41 @@ impl<F: AuthFlow, V: Verifier> Builder<'_, F, V, FollowUserOrArtistEndpoint> {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_page_keeps_the_time_range() {
+        let next = "https://api.spotify.com/v1/me/top/tracks?limit=20&offset=20".to_owned();
+
+        assert_eq!(
+            with_time_range(next, &TimeRange::ShortTerm),
+            "https://api.spotify.com/v1/me/top/tracks?limit=20&offset=20&time_range=short_term"
+        );
+    }
+
+    #[test]
+    fn existing_time_range_is_kept() {
+        let next =
+            "https://api.spotify.com/v1/me/top/tracks?time_range=long_term&offset=20".to_owned();
+
+        assert_eq!(with_time_range(next.clone(), &TimeRange::ShortTerm), next);
+    }
+
+    #[test]
+    fn top_items_query() {
+        let endpoint = UserTopItemsEndpoint {
+            r#type: UserItemType::Tracks,
+            time_range: Some(TimeRange::ShortTerm),
+            limit: Some(Limit::new(50)),
+            offset: Some(50),
+        };
+
+        assert_eq!(
+            serde_json::to_value(endpoint).unwrap(),
+            json!({ "time_range": "short_term", "limit": 50, "offset": 50 })
+        );
+    }
+}
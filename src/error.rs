@@ -1,24 +1,54 @@
+use std::sync::Arc;
+
 use oauth2::{basic::BasicErrorResponseType, RequestTokenError, StandardErrorResponse};
 
+use reqwest::header::{HeaderMap, RETRY_AFTER, WWW_AUTHENTICATE};
 use serde::Deserialize;
 use thiserror::Error;
 
+use crate::auth::CallbackError;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// An error returned by the client in a custom [`Result`].
 #[derive(Clone, Debug, Error)]
 pub enum Error {
     /// Error that occured during authentication.
+    ///
+    /// `source` is the underlying error returned by the [`oauth2`] crate.
     #[error("An error occured during authentication: {description}")]
-    Authentication { kind: Kind, description: String },
+    Authentication {
+        kind: Kind,
+        description: String,
+        source: Arc<dyn std::error::Error + Send + Sync>,
+    },
 
     /// The token has expired and auto-refresh is turned off.
     #[error("The access token has expired and auto-refresh is turned off.")]
     ExpiredToken,
 
+    /// The response couldn't be deserialized into the expected type.
+    ///
+    /// `body` contains the raw response body, which is useful for figuring out what went wrong.
+    #[error("Failed to deserialize the response: {source}")]
+    Deserialization {
+        source: Arc<serde_json::Error>,
+        body: String,
+    },
+
     /// HTTP error returned from the underlying HTTP client.
-    #[error("{0}")]
-    Http(String),
+    #[error(transparent)]
+    Http(Arc<reqwest::Error>),
+
+    /// The user denied access to the app when authorising it (the reason is usually
+    /// `access_denied`), or authorisation failed for another reason.
+    #[error("Authorisation was denied: {reason}")]
+    AuthorizationDenied { reason: String },
+
+    /// The URL the user was redirected to after authorising the app doesn't contain
+    /// an auth code or state.
+    #[error(transparent)]
+    Callback(CallbackError),
 
     /// The (CSRF) state parameter supplied is not the same as the one initially generated and sent to the server.
     ///
@@ -28,6 +58,19 @@ pub enum Error {
     )]
     InvalidStateParameter,
 
+    /// The image can't be uploaded (e.g. it's not a JPEG or it's too large).
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+
+    /// An error returned by a middleware of the transport.
+    #[cfg(feature = "middleware")]
+    #[error(transparent)]
+    Middleware(Arc<dyn std::error::Error + Send + Sync>),
+
+    /// An I/O error, e.g. when reading an image to upload.
+    #[error(transparent)]
+    Io(Arc<std::io::Error>),
+
     /// The client has not yet been authenticated.
     #[error("The client has not been authenticated.")]
     NotAuthenticated,
@@ -36,9 +79,55 @@ pub enum Error {
     #[error("The access token has has expired and refreshing it is not available in the current authorisation flow.")]
     RefreshUnavailable,
 
+    /// A playback offset was supplied without a context URI. Offsets are only supported
+    /// when playing an album or playlist context, not a list of URIs.
+    #[error("A playback offset can only be used together with a context URI.")]
+    OffsetWithoutContext,
+
+    /// Nothing is currently playing (or its progress is unknown), so there's no position to seek from.
+    #[error("Nothing is currently playing.")]
+    NothingPlaying,
+
+    /// More than 5 seeds (artists, genres and tracks combined) were supplied for recommendations.
+    #[error("Too many recommendation seeds: {count} were supplied, but at most 5 are allowed.")]
+    TooManySeeds { count: usize },
+
+    /// A server error (`5xx`) returned from Spotify, for endpoints that are known to fail
+    /// on Spotify's end, such as the chapter endpoints.
+    #[error("Spotify's server failed to handle the request: {status} {}", .message.as_deref().unwrap_or("(no message)"))]
+    UpstreamServerError {
+        status: u16,
+        message: Option<String>,
+    },
+
+    /// An error response from Spotify whose body isn't JSON (e.g. an HTML error page).
+    ///
+    /// Unauthorised (`401`) and rate limiting (`429`) errors are always returned as [`Error::Api`] instead,
+    /// so they can be handled the same way regardless of the body.
+    #[error("Error returned from the Spotify API: {status} (non-JSON body)")]
+    NonJsonError {
+        status: u16,
+        body: String,
+        /// The amount of seconds to wait before retrying, taken from the `Retry-After` header.
+        retry_after: Option<u64>,
+        /// The headers of the response.
+        headers: Box<HeaderMap>,
+    },
+
     /// An error returned from Spotify.
-    #[error("Error returned from the Spotify API: {status} {message}")]
-    Spotify { status: u16, message: String },
+    ///
+    /// `message` is `None` if the response body didn't contain a Spotify error object
+    /// (e.g. it was empty).
+    #[error("Error returned from the Spotify API: {status} {}", .message.as_deref().unwrap_or("(no message)"))]
+    Api {
+        status: u16,
+        message: Option<String>,
+        /// The amount of seconds to wait before retrying, taken from the `Retry-After` header.
+        /// It's usually present when being rate limited (status `429`).
+        retry_after: Option<u64>,
+        /// The headers of the response.
+        headers: Box<HeaderMap>,
+    },
 }
 
 #[derive(Deserialize)]
@@ -48,7 +137,6 @@ pub(crate) struct SpotifyError {
 
 #[derive(Deserialize)]
 struct Details {
-    status: u16,
     message: String,
 }
 
@@ -79,7 +167,7 @@ impl
             StandardErrorResponse<BasicErrorResponseType>,
         >,
     ) -> Self {
-        match value {
+        let (kind, description) = match &value {
             RequestTokenError::ServerResponse(res) => {
                 let additional = match res.error_description() {
                     Some(desc) => format!(": {desc}"),
@@ -96,31 +184,181 @@ impl
                     BasicErrorResponseType::Extension(desc) => desc,
                 };
 
-                let description = format!("{base}{additional}");
-
-                Error::Authentication {
-                    kind: Kind::ServerResponse,
-                    description,
-                }
+                (Kind::ServerResponse, format!("{base}{additional}"))
             }
-            RequestTokenError::Request(err) => Error::Authentication { kind: Kind::Request, description: format!("An error occured while sending the request or receiving the response from the authentication server: {err}") },
-            RequestTokenError::Parse(err, _) => Error::Authentication { kind: Kind::Parse, description: format!("Failed to parse server response: {err}") },
-            RequestTokenError::Other(err) => Error::Authentication { kind: Kind::Unknown, description: format!("An unknown error occured: {err}") },
+            RequestTokenError::Request(_) => (Kind::Request, "An error occured while sending the request or receiving the response from the authentication server.".to_owned()),
+            RequestTokenError::Parse(..) => (Kind::Parse, "Failed to parse server response.".to_owned()),
+            RequestTokenError::Other(_) => (Kind::Unknown, "An unknown error occured.".to_owned()),
+        };
+
+        Error::Authentication {
+            kind,
+            description,
+            source: Arc::new(value),
+        }
+    }
+}
+
+impl From<CallbackError> for Error {
+    fn from(value: CallbackError) -> Self {
+        match value {
+            CallbackError::AuthorizationDenied { reason } => Self::AuthorizationDenied { reason },
+            other => Self::Callback(other),
         }
     }
 }
 
 impl From<reqwest::Error> for Error {
     fn from(value: reqwest::Error) -> Self {
-        Self::Http(value.to_string())
+        Self::Http(Arc::new(value))
     }
 }
 
-impl From<SpotifyError> for Error {
-    fn from(value: SpotifyError) -> Self {
-        Self::Spotify {
-            status: value.error.status,
-            message: value.error.message,
+#[cfg(feature = "middleware")]
+impl From<reqwest_middleware::Error> for Error {
+    fn from(value: reqwest_middleware::Error) -> Self {
+        match value {
+            reqwest_middleware::Error::Reqwest(err) => err.into(),
+            reqwest_middleware::Error::Middleware(err) => {
+                Self::Middleware(Arc::from(Box::<dyn std::error::Error + Send + Sync>::from(
+                    err,
+                )))
+            }
         }
     }
 }
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(Arc::new(value))
+    }
+}
+
+impl Error {
+    /// Turn server errors (`5xx`) into [`Error::UpstreamServerError`], leaving other errors as they are.
+    ///
+    /// This includes server errors whose body isn't JSON (e.g. an HTML error page), which don't have a message.
+    pub(crate) fn upstream(self) -> Self {
+        match self {
+            Self::Api {
+                status, message, ..
+            } if (500..600).contains(&status) => Self::UpstreamServerError { status, message },
+            Self::NonJsonError { status, .. } if (500..600).contains(&status) => {
+                Self::UpstreamServerError {
+                    status,
+                    message: None,
+                }
+            }
+            other => other,
+        }
+    }
+
+    /// Whether Spotify rejected the access token (e.g. because it expired or was revoked),
+    /// in which case refreshing it may help.
+    pub(crate) fn is_invalid_token(&self) -> bool {
+        let Self::Api {
+            status: 401,
+            message,
+            headers,
+            ..
+        } = self
+        else {
+            return false;
+        };
+
+        let expired = message.as_deref().is_some_and(|message| {
+            matches!(message, "The access token expired" | "Invalid access token")
+        });
+        let invalid = headers
+            .get(WWW_AUTHENTICATE)
+            .and_then(|header| header.to_str().ok())
+            .is_some_and(|header| header.contains("invalid_token"));
+
+        expired || invalid
+    }
+
+    pub(crate) fn deserialization(source: serde_json::Error, body: String) -> Self {
+        Self::Deserialization {
+            source: Arc::new(source),
+            body,
+        }
+    }
+
+    pub(crate) async fn from_response(res: reqwest::Response) -> Self {
+        let status = res.status().as_u16();
+        let headers = Box::new(res.headers().clone());
+
+        let retry_after = headers
+            .get(RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok());
+
+        // The body isn't guaranteed to be JSON, so failing to read or parse it
+        // shouldn't hide the status and headers.
+        let body = res.text().await.unwrap_or_default();
+        let message = serde_json::from_str::<SpotifyError>(&body)
+            .ok()
+            .map(|e| e.error.message);
+
+        let is_json = serde_json::from_str::<serde::de::IgnoredAny>(&body).is_ok();
+
+        if !is_json && !body.trim().is_empty() && status != 401 && status != 429 {
+            return Self::NonJsonError {
+                status,
+                body,
+                retry_after,
+                headers,
+            };
+        }
+
+        Self::Api {
+            status,
+            message,
+            retry_after,
+            headers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The messages of the error and its sources, from the outermost.
+    fn chain(err: &Error) -> Vec<String> {
+        std::iter::successors(Some(err as &dyn std::error::Error), |err| (*err).source())
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn messages_dont_repeat_their_source() {
+        let io = Error::from(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "missing.jpg",
+        ));
+        let auth = Error::from(RequestTokenError::Request(oauth2::reqwest::Error::<
+            reqwest::Error,
+        >::Other(
+            "connection reset".to_owned()
+        )));
+
+        assert_eq!(chain(&io), ["missing.jpg"]);
+
+        let messages = chain(&auth);
+        assert_eq!(
+            messages
+                .iter()
+                .filter(|m| m.contains("connection reset"))
+                .count(),
+            1
+        );
+        assert!(matches!(
+            auth,
+            Error::Authentication {
+                kind: Kind::Request,
+                ..
+            }
+        ));
+    }
+}
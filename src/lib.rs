@@ -28,9 +28,9 @@
 //!
 //! The auth flow you should use depends on the use case:
 //! - the authorisation code flow is recommended for long-running applications
-//! where you can safely store the client secret (e.g. web and mobile apps)
+//!   where you can safely store the client secret (e.g. web and mobile apps)
 //! - the authorisation code with PKCE flow is recommended for long-running applications
-//! where you *can't* safely store the client secret (e.g. desktop apps and single page web apps)
+//!   where you *can't* safely store the client secret (e.g. desktop apps and single page web apps)
 //! - the client credentials flow doesn't include authorisation, thus letting you only access public information
 //!
 //! Below is an example for each auth flow:
@@ -54,7 +54,7 @@
 //!     // with those two parameters present in the URL
 //!
 //!     // Finally, exchange the auth code for an access token
-//!     let mut spotify = client.authenticate("auth_code", "csrf_token").await?;
+//!     let spotify = client.authenticate("auth_code", "csrf_token").await?;
 //!
 //!     // Get an album with the specified ID (requires no scopes to be set)
 //!     let album = spotify.album("album_id").get().await?;
@@ -79,7 +79,8 @@
 //! represents the endpoint you're calling.
 //!
 //! The auth code and CSRF token can be obtained by parsing the URL the user was redirected
-//! to from the `url` returned from `.get_authorisation`.
+//! to from the `url` returned from `.get_authorisation`, which
+//! [`auth::parse_callback`] does for you.
 //!
 //! That could be achieved by simply having the user copy and paste the URL into your app,
 //! or, the recommended approach, by having a server listening at your `redirect_url` and
@@ -95,7 +96,7 @@
 //!     let auth_flow = ClientCredsFlow::new("client_id", "client_secret");
 //!
 //!     // Create an authenticate the client
-//!     let mut spotify = ClientCredsClient::authenticate(auth_flow).await?;
+//!     let spotify = ClientCredsClient::authenticate(auth_flow).await?;
 //!
 //!     let album = spotify.album("album_id").get().await?;
 //!
@@ -116,35 +117,45 @@
 //!
 //! If you disable this feature, you'll have to refresh the token yourself using [`Client::request_refresh_token()`].
 //!
+//! To keep the token between runs of your app, set a [`TokenStore`](store::TokenStore) with
+//! [`Client::set_token_store()`], such as the `FileTokenStore` enabled by the `token-store` feature.
+//!
 //! [`AuthCodePkceFlow`]: auth::AuthCodePkceFlow
 //! [`Builder`]: endpoint::Builder
 //! [`Client::request_refresh_token()`]: client::Client::request_refresh_token()
+//! [`Client::set_token_store()`]: client::Client::set_token_store()
 
 pub mod auth;
 pub mod client;
 pub mod endpoint;
 mod error;
 pub mod model;
+pub mod store;
+pub mod transport;
 
 use client::Body;
 use serde::{Deserialize, Deserializer};
 
-pub(crate) fn query_list<T: AsRef<str>>(list: &[T]) -> String {
-    list.iter()
-        .map(|i| i.as_ref())
-        .collect::<Vec<&str>>()
+pub(crate) fn query_list(list: impl IntoIterator<Item = impl AsRef<str>>) -> String {
+    list.into_iter()
+        .map(|i| i.as_ref().to_owned())
+        .collect::<Vec<String>>()
         .join(",")
 }
 
-pub(crate) fn body_list<T: AsRef<str>>(name: &str, list: &[T]) -> Body<serde_json::Value> {
-    let list: Vec<_> = list.iter().map(|i| i.as_ref()).collect();
+pub(crate) fn body_list(
+    name: &str,
+    list: impl IntoIterator<Item = impl AsRef<str>>,
+) -> Body<serde_json::Value> {
+    let list: Vec<_> = list.into_iter().map(|i| i.as_ref().to_owned()).collect();
     Body::Json(serde_json::json!({ name: list }))
 }
 
-pub use auth::{AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow};
+pub use auth::{AuthCodeFlow, AuthCodePkceFlow, ClientCredsFlow, Scope};
 pub use client::{AuthCodeClient, AuthCodePkceClient, ClientCredsClient};
 pub use error::{Error, Result as SpotifyResult};
-pub use oauth2::RedirectUrl;
+pub use oauth2::{AuthUrl, RedirectUrl, TokenUrl};
+pub use reqwest::Url;
 
 /// Represents an empty API response.
 pub struct Nil;
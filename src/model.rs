@@ -1,4 +1,21 @@
-use serde::Deserialize;
+use std::{
+    fmt::Display,
+    future::Future,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+
+use chrono::{Datelike, NaiveDate};
+use futures_util::{future, stream, Stream, StreamExt, TryStreamExt};
+use reqwest::{Method, Url};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer};
+use serde_json::Value;
+
+use crate::{
+    auth::{AuthFlow, Token, Verifier},
+    client::Client,
+    error::{Error, Result},
+};
 
 pub mod album;
 pub mod artist;
@@ -12,9 +29,11 @@ pub mod recommendation;
 pub mod search;
 pub mod show;
 pub mod track;
+pub mod uri;
 pub mod user;
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Page<T> {
     pub href: String,
     pub limit: u32,
@@ -25,7 +44,135 @@ pub struct Page<T> {
     pub items: Vec<T>,
 }
 
+impl<T> Page<T> {
+    /// Iterate over the items in the page, without consuming it.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.items.iter()
+    }
+}
+
+impl<T: DeserializeOwned> Page<T> {
+    /// Get the next page, or `None` if this is the last page.
+    pub async fn get_next<F: AuthFlow, V: Verifier>(
+        &self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Option<Page<T>>> {
+        next_page(self.next.clone(), spotify).await
+    }
+
+    /// Get the items of all the pages after this one, requesting them one by one.
+    pub async fn get_remaining<F: AuthFlow, V: Verifier>(
+        &self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Vec<T>> {
+        remaining_items::<Self, F, V>(self.next.clone(), spotify).await
+    }
+
+    /// Get the items of this page and all the pages after it, requesting them one by one.
+    pub async fn get_all<F: AuthFlow, V: Verifier>(
+        self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Vec<T>> {
+        let remaining = self.get_remaining(spotify).await?;
+        let mut items = self.items;
+        items.extend(remaining);
+
+        Ok(items)
+    }
+
+    /// Turn the page into a stream of its items and the items of all the pages after it.
+    ///
+    /// The pages are only requested once the items of the previous page have been consumed.
+    pub fn into_stream<F: AuthFlow, V: Verifier>(
+        self,
+        spotify: &Client<Token, F, V>,
+    ) -> impl Stream<Item = Result<T>> + '_
+    where
+        T: 'static,
+    {
+        items_stream::<Self, F, V>(self.items, self.next, spotify)
+    }
+
+    /// Get the items of this page and all the pages after it, requesting up to
+    /// `concurrency` pages at the same time.
+    ///
+    /// The offsets of the remaining pages are computed using this page's `total` and `limit`,
+    /// and the items are returned in order. If the offsets can't be computed
+    /// (e.g. the `limit` is 0), this falls back to [`get_all`](Self::get_all).
+    pub async fn get_all_concurrent<F: AuthFlow, V: Verifier>(
+        self,
+        spotify: &Client<Token, F, V>,
+        concurrency: usize,
+    ) -> Result<Vec<T>> {
+        let Some(urls) = self.remaining_page_urls() else {
+            return self.get_all(spotify).await;
+        };
+
+        spotify.ensure_valid_token().await?;
+
+        let pages: Vec<Page<T>> = stream::iter(urls)
+            .map(|url| fetch_page(spotify, url))
+            .buffered(concurrency.max(1))
+            .try_collect()
+            .await?;
+
+        let mut items = self.items;
+        items.extend(pages.into_iter().flat_map(|p| p.items));
+
+        Ok(items)
+    }
+
+    /// The URLs of all the pages after this one, based on this page's URL, offset and limit.
+    fn remaining_page_urls(&self) -> Option<Vec<String>> {
+        if self.limit == 0 || self.next.is_none() {
+            return None;
+        }
+
+        let href = Url::parse(&self.href).ok()?;
+        let offsets = (self.offset + self.limit..self.total).step_by(self.limit as usize);
+
+        let urls = offsets
+            .map(|offset| {
+                let mut url = href.clone();
+                let query: Vec<_> = href
+                    .query_pairs()
+                    .filter(|(k, _)| k != "offset" && k != "limit")
+                    .collect();
+
+                url.query_pairs_mut()
+                    .clear()
+                    .extend_pairs(query)
+                    .append_pair("offset", &offset.to_string())
+                    .append_pair("limit", &self.limit.to_string());
+
+                url.to_string()
+            })
+            .collect();
+
+        Some(urls)
+    }
+}
+
+impl<T> IntoIterator for Page<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Page<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct CursorPage<T> {
     pub href: String,
     pub limit: u32,
@@ -35,31 +182,224 @@ pub struct CursorPage<T> {
     pub items: Vec<T>,
 }
 
+impl<T> CursorPage<T> {
+    /// The `after` cursor, which can be used to request the items after the ones on this page.
+    pub fn next_after(&self) -> Option<&str> {
+        self.cursors.after.as_deref()
+    }
+
+    /// The `before` cursor, which can be used to request the items before the ones on this page.
+    pub fn next_before(&self) -> Option<&str> {
+        self.cursors.before.as_deref()
+    }
+}
+
+impl<T: DeserializeOwned> CursorPage<T> {
+    /// Get the next page (which is based on the `after` or `before` cursor),
+    /// or `None` if this is the last page.
+    pub async fn get_next<F: AuthFlow, V: Verifier>(
+        &self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Option<CursorPage<T>>> {
+        next_page(self.next.clone(), spotify).await
+    }
+
+    /// Get the items of all the pages after this one, requesting them one by one.
+    pub async fn get_remaining<F: AuthFlow, V: Verifier>(
+        &self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Vec<T>> {
+        remaining_items::<Self, F, V>(self.next.clone(), spotify).await
+    }
+
+    /// Get the items of this page and all the pages after it, requesting them one by one.
+    pub async fn get_all<F: AuthFlow, V: Verifier>(
+        self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Vec<T>> {
+        let remaining = self.get_remaining(spotify).await?;
+        let mut items = self.items;
+        items.extend(remaining);
+
+        Ok(items)
+    }
+
+    /// Turn the page into a stream of its items and the items of all the pages after it.
+    ///
+    /// The pages are only requested once the items of the previous page have been consumed.
+    pub fn into_stream<F: AuthFlow, V: Verifier>(
+        self,
+        spotify: &Client<Token, F, V>,
+    ) -> impl Stream<Item = Result<T>> + '_
+    where
+        T: 'static,
+    {
+        items_stream::<Self, F, V>(self.items, self.next, spotify)
+    }
+}
+
+/// A page of items, which links to the next page.
+trait Paginated: DeserializeOwned {
+    type Item;
+
+    fn into_parts(self) -> (Vec<Self::Item>, Option<String>);
+}
+
+impl<T: DeserializeOwned> Paginated for Page<T> {
+    type Item = T;
+
+    fn into_parts(self) -> (Vec<T>, Option<String>) {
+        (self.items, self.next)
+    }
+}
+
+impl<T: DeserializeOwned> Paginated for CursorPage<T> {
+    type Item = T;
+
+    fn into_parts(self) -> (Vec<T>, Option<String>) {
+        (self.items, self.next)
+    }
+}
+
+/// Request the page at the given URL, without checking whether the token has expired.
+///
+/// Some endpoints wrap their pages in an object (e.g. `{ "artists": { ... } }`),
+/// in which case the page is unwrapped first.
+async fn fetch_page<P: DeserializeOwned, F: AuthFlow, V: Verifier>(
+    spotify: &Client<Token, F, V>,
+    url: String,
+) -> Result<P> {
+    let page = match spotify.send::<(), _>(Method::GET, url, None, None).await? {
+        Value::Object(map) if !map.contains_key("items") => {
+            let page = map.values().find(|v| v.get("items").is_some()).cloned();
+            page.unwrap_or(Value::Object(map))
+        }
+        page => page,
+    };
+
+    P::deserialize(&page).map_err(|e| Error::deserialization(e, page.to_string()))
+}
+
+async fn next_page<P: Paginated, F: AuthFlow, V: Verifier>(
+    next: Option<String>,
+    spotify: &Client<Token, F, V>,
+) -> Result<Option<P>> {
+    let Some(next) = next else {
+        return Ok(None);
+    };
+
+    spotify.ensure_valid_token().await?;
+    fetch_page(spotify, next).await.map(Some)
+}
+
+async fn remaining_items<P: Paginated, F: AuthFlow, V: Verifier>(
+    mut next: Option<String>,
+    spotify: &Client<Token, F, V>,
+) -> Result<Vec<P::Item>> {
+    let mut items = vec![];
+
+    while let Some(page) = next_page::<P, F, V>(next, spotify).await? {
+        let (page_items, page_next) = page.into_parts();
+        items.extend(page_items);
+        next = page_next;
+    }
+
+    Ok(items)
+}
+
+/// A stream of the items of the first page (which is only requested once the stream is polled)
+/// and of all the pages after it.
+pub(crate) fn page_stream<'s, T, F: AuthFlow, V: Verifier>(
+    first_page: impl Future<Output = Result<Page<T>>> + 's,
+    spotify: &'s Client<Token, F, V>,
+) -> impl Stream<Item = Result<T>> + 's
+where
+    T: DeserializeOwned + 'static,
+{
+    stream::once(first_page).flat_map(move |page| match page {
+        Ok(page) => page.into_stream(spotify).left_stream(),
+        Err(e) => stream::once(future::ready(Err(e))).right_stream(),
+    })
+}
+
+fn items_stream<P, F: AuthFlow, V: Verifier>(
+    items: Vec<P::Item>,
+    next: Option<String>,
+    spotify: &Client<Token, F, V>,
+) -> impl Stream<Item = Result<P::Item>> + '_
+where
+    P: Paginated,
+    P::Item: 'static,
+{
+    stream::unfold(
+        (spotify, items.into_iter(), next),
+        |(spotify, mut items, mut next)| async move {
+            loop {
+                if let Some(item) = items.next() {
+                    return Some((Ok(item), (spotify, items, next)));
+                }
+
+                match next_page::<P, F, V>(next.take(), spotify).await {
+                    Ok(Some(page)) => {
+                        let (page_items, page_next) = page.into_parts();
+                        items = page_items.into_iter();
+                        next = page_next;
+                    }
+                    Ok(None) => return None,
+                    // Stop after the error, since there's no way to get the next page.
+                    Err(e) => return Some((Err(e), (spotify, Vec::new().into_iter(), None))),
+                }
+            }
+        },
+    )
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Cursor {
     pub after: Option<String>,
     pub before: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Image {
     pub url: String,
     pub height: Option<u32>,
     pub width: Option<u32>,
 }
 
+impl Image {
+    /// The largest of the images, by area. Images with unknown dimensions are considered the smallest.
+    pub fn largest(images: &[Image]) -> Option<&Image> {
+        images.iter().max_by_key(|i| i.area())
+    }
+
+    /// The smallest of the images, by area. Images with unknown dimensions are considered the smallest.
+    pub fn smallest(images: &[Image]) -> Option<&Image> {
+        images.iter().min_by_key(|i| i.area())
+    }
+
+    fn area(&self) -> u64 {
+        u64::from(self.width.unwrap_or(0)) * u64::from(self.height.unwrap_or(0))
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Copyright {
     pub text: String,
     pub r#type: CopyrightType,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Restrictions {
     pub reason: RestrictionReason,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct ExternalIds {
     pub isrc: Option<String>,
     pub ean: Option<String>,
@@ -67,11 +407,13 @@ pub struct ExternalIds {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct ExternalUrls {
     pub spotify: String,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Followers {
     /// This will always be set to null, as the Web API does not support it at the moment.
     pub href: Option<String>,
@@ -79,11 +421,19 @@ pub struct Followers {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct ResumePoint {
     pub fully_played: bool,
     pub resume_position_ms: u32,
 }
 
+impl ResumePoint {
+    /// The position at which the playback was last stopped.
+    pub fn resume_position(&self) -> Duration {
+        Duration::from_millis(self.resume_position_ms.into())
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RestrictionReason {
@@ -94,12 +444,14 @@ pub enum RestrictionReason {
     Unknown,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
 pub enum CopyrightType {
     #[serde(rename = "C")]
     Copyright,
     #[serde(rename = "P")]
     Performance,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -108,6 +460,128 @@ pub enum DatePrecision {
     Year,
     Month,
     Day,
+    #[serde(other)]
+    Unknown,
+}
+
+/// A release date, as precise as Spotify knows it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ReleaseDate {
+    Year(i32),
+    YearMonth(i32, u32),
+    Full(NaiveDate),
+}
+
+impl ReleaseDate {
+    /// Parse a release date (`1981`, `1981-12` or `1981-12-15`) according to its precision.
+    pub(crate) fn parse(date: &str, precision: &DatePrecision) -> Option<Self> {
+        let mut parts = date.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+
+        match precision {
+            DatePrecision::Year => Some(Self::Year(year)),
+            DatePrecision::Month => {
+                let month = parts.next()?.parse().ok()?;
+                (1..=12)
+                    .contains(&month)
+                    .then_some(Self::YearMonth(year, month))
+            }
+            DatePrecision::Day => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .ok()
+                .map(Self::Full),
+            DatePrecision::Unknown => None,
+        }
+    }
+
+    /// The year of the release.
+    pub fn year(&self) -> i32 {
+        match self {
+            Self::Year(year) | Self::YearMonth(year, _) => *year,
+            Self::Full(date) => date.year(),
+        }
+    }
+}
+
+/// The text of the first copyright of the given type.
+pub(crate) fn copyright_text(copyrights: &[Copyright], r#type: CopyrightType) -> Option<&str> {
+    copyrights
+        .iter()
+        .find(|c| c.r#type == r#type)
+        .map(|c| c.text.as_str())
+}
+
+/// Returns `true` if the market (an ISO 3166-1 alpha-2 country code, in any case)
+/// is in the list of markets.
+///
+/// Spotify leaves out the available markets when a market is specified in the request,
+/// as it only returns the items available there, so an empty list counts as available.
+pub(crate) fn is_available_in(available_markets: &[String], market: &str) -> bool {
+    available_markets.is_empty()
+        || available_markets
+            .iter()
+            .any(|m| m.eq_ignore_ascii_case(market))
+}
+
+/// Normalise the languages (e.g. `en-US` or `EN`) into lowercase ISO 639 codes (e.g. `en`),
+/// skipping the ones that aren't valid and any duplicates.
+pub(crate) fn language_codes(languages: &[String]) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+
+    for language in languages {
+        let code = language.split(['-', '_']).next().unwrap_or_default();
+
+        if !(2..=3).contains(&code.len()) || !code.chars().all(|c| c.is_ascii_alphabetic()) {
+            continue;
+        }
+
+        let code = code.to_ascii_lowercase();
+
+        if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+
+    codes
+}
+
+/// Deserialize a list, skipping the items that can't be deserialized
+/// (instead of failing to deserialize the whole response).
+pub(crate) fn deserialize_skip_invalid<'de, D, T>(
+    deserializer: D,
+) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let values = Vec::<serde_json::Value>::deserialize(deserializer)?;
+
+    Ok(values
+        .into_iter()
+        .filter_map(|v| T::deserialize(v).ok())
+        .collect())
+}
+
+/// Deserialize a value, treating `null` as the type's default value (e.g. an empty list).
+pub(crate) fn deserialize_null_as_default<'de, D, T>(
+    deserializer: D,
+) -> std::result::Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// Deserialize an optional value, treating a value that can't be deserialized as `None`.
+pub(crate) fn deserialize_invalid_as_none<'de, D, T>(
+    deserializer: D,
+) -> std::result::Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.and_then(|v| T::deserialize(v).ok()))
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -115,4 +589,252 @@ pub enum DatePrecision {
 pub enum PlayableItem {
     Track(track::Track),
     Episode(show::Episode),
+    /// A local file from the user's device, which isn't available on Spotify.
+    Local(track::LocalTrack),
+}
+
+impl PlayableItem {
+    /// The track, if this item is a track.
+    pub fn as_track(&self) -> Option<&track::Track> {
+        match self {
+            Self::Track(track) => Some(track),
+            _ => None,
+        }
+    }
+
+    /// The episode, if this item is an episode.
+    pub fn as_episode(&self) -> Option<&show::Episode> {
+        match self {
+            Self::Episode(episode) => Some(episode),
+            _ => None,
+        }
+    }
+
+    /// The local track, if this item is a local file.
+    pub fn as_local(&self) -> Option<&track::LocalTrack> {
+        match self {
+            Self::Local(track) => Some(track),
+            _ => None,
+        }
+    }
+
+    /// The name of the item.
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Track(track) => &track.name,
+            Self::Episode(episode) => &episode.name,
+            Self::Local(track) => &track.name,
+        }
+    }
+
+    /// The Spotify URI of the item.
+    pub fn uri(&self) -> &str {
+        match self {
+            Self::Track(track) => &track.uri,
+            Self::Episode(episode) => &episode.uri,
+            Self::Local(track) => &track.uri,
+        }
+    }
+
+    /// The duration of the item.
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Track(track) => track.duration(),
+            Self::Episode(episode) => episode.duration(),
+            Self::Local(track) => track.duration(),
+        }
+    }
+
+    /// The Spotify ID of the item. Local files don't have an ID, so this is `None` for them.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Self::Track(track) => Some(&track.id),
+            Self::Episode(episode) => Some(&episode.id),
+            Self::Local(_) => None,
+        }
+    }
+}
+
+impl Display for PlayableItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Implements `PartialEq`, `Eq` and `Hash` based on the Spotify ID only, so that e.g. two
+/// tracks that only differ in the order of their markets are considered equal.
+macro_rules! impl_id_identity {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl PartialEq for $ty {
+                fn eq(&self, other: &Self) -> bool {
+                    self.id == other.id
+                }
+            }
+
+            impl Eq for $ty {}
+
+            impl Hash for $ty {
+                fn hash<H: Hasher>(&self, state: &mut H) {
+                    self.id.hash(state);
+                }
+            }
+        )*
+    };
+}
+
+impl_id_identity!(
+    album::Album,
+    album::SimplifiedAlbum,
+    artist::Artist,
+    artist::SimplifiedArtist,
+    audiobook::Audiobook,
+    audiobook::SimplifiedAudiobook,
+    audiobook::Chapter,
+    audiobook::SimplifiedChapter,
+    category::Category,
+    playlist::Playlist,
+    playlist::SimplifiedPlaylist,
+    show::Show,
+    show::SimplifiedShow,
+    show::Episode,
+    show::SimplifiedEpisode,
+    track::Track,
+    track::SimplifiedTrack,
+    user::PrivateUser,
+    user::User,
+    user::ReferenceUser,
+);
+
+/// A Spotify object which links to its page on Spotify, such as a track or an album.
+pub trait HasExternalUrls {
+    /// The object's external URLs.
+    fn external_urls(&self) -> &ExternalUrls;
+
+    /// The object's Spotify URL (e.g. `https://open.spotify.com/track/...`), which can be shared.
+    fn spotify_url(&self) -> &str {
+        &self.external_urls().spotify
+    }
+}
+
+/// A Spotify object with an ID, such as a track, an album or a user.
+///
+/// Useful for writing code that works with any kind of object, e.g. caching objects by their ID.
+pub trait SpotifyObject: HasExternalUrls {
+    /// The object's [Spotify ID](https://developer.spotify.com/documentation/web-api/concepts/spotify-uris-ids).
+    fn id(&self) -> &str;
+
+    /// The object's [Spotify URI](https://developer.spotify.com/documentation/web-api/concepts/spotify-uris-ids).
+    fn uri(&self) -> &str;
+
+    /// The API endpoint which returns the full details of the object.
+    fn href(&self) -> &str;
+}
+
+impl HasExternalUrls for player::Context {
+    fn external_urls(&self) -> &ExternalUrls {
+        &self.external_urls
+    }
+}
+
+macro_rules! impl_languages {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $ty {
+                /// The languages as lowercase [ISO 639](https://en.wikipedia.org/wiki/ISO_639) codes
+                /// (e.g. `en-US` becomes `en`), skipping invalid ones and duplicates.
+                pub fn language_codes(&self) -> Vec<String> {
+                    language_codes(&self.languages)
+                }
+
+                /// The first language as a lowercase ISO 639 code, if there's a valid one.
+                pub fn primary_language(&self) -> Option<String> {
+                    self.language_codes().into_iter().next()
+                }
+            }
+        )*
+    };
+}
+
+impl_languages!(
+    show::Show,
+    show::SimplifiedShow,
+    show::Episode,
+    show::SimplifiedEpisode,
+);
+
+macro_rules! impl_spotify_object {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl HasExternalUrls for $ty {
+                fn external_urls(&self) -> &ExternalUrls {
+                    &self.external_urls
+                }
+            }
+
+            impl SpotifyObject for $ty {
+                fn id(&self) -> &str {
+                    &self.id
+                }
+
+                fn uri(&self) -> &str {
+                    &self.uri
+                }
+
+                fn href(&self) -> &str {
+                    &self.href
+                }
+            }
+        )*
+    };
+}
+
+impl_spotify_object!(
+    album::Album,
+    album::SimplifiedAlbum,
+    artist::Artist,
+    artist::SimplifiedArtist,
+    audiobook::Audiobook,
+    audiobook::SimplifiedAudiobook,
+    audiobook::Chapter,
+    audiobook::SimplifiedChapter,
+    playlist::Playlist,
+    playlist::SimplifiedPlaylist,
+    show::Show,
+    show::SimplifiedShow,
+    show::Episode,
+    show::SimplifiedEpisode,
+    track::Track,
+    track::SimplifiedTrack,
+    track::LinkedFrom,
+    user::PrivateUser,
+    user::User,
+    user::ReferenceUser,
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn availability() {
+        let markets = ["SE".to_owned(), "US".to_owned()];
+
+        assert!(is_available_in(&markets, "SE"));
+        assert!(is_available_in(&markets, "us"));
+        assert!(!is_available_in(&markets, "GB"));
+        // Unknown markets count as available.
+        assert!(is_available_in(&[], "GB"));
+    }
+
+    #[test]
+    fn normalised_language_codes() {
+        let languages = [
+            "en-US", "EN", "pt_BR", "fil", "es-419", "", "e", "english", "1a",
+        ]
+        .map(String::from);
+
+        assert_eq!(language_codes(&languages), ["en", "pt", "fil", "es"]);
+        assert!(language_codes(&[]).is_empty());
+    }
 }
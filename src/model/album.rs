@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use super::{artist::SimplifiedArtist, track::SimplifiedTrack, *};
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Album {
     pub album_type: AlbumType,
     pub total_tracks: u32,
@@ -27,7 +28,53 @@ pub struct Album {
     pub tracks: Page<SimplifiedTrack>,
 }
 
+impl Album {
+    /// Get all of the album's tracks, using the embedded page and fetching the remaining pages.
+    pub async fn all_tracks<F: AuthFlow, V: Verifier>(
+        &self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Vec<SimplifiedTrack>> {
+        let mut tracks = self.tracks.items.clone();
+        tracks.extend(self.tracks.get_remaining(spotify).await?);
+
+        Ok(tracks)
+    }
+
+    /// The text of the album's copyright (©), if any.
+    pub fn copyright_text(&self) -> Option<&str> {
+        copyright_text(&self.copyrights, CopyrightType::Copyright)
+    }
+
+    /// The text of the album's sound recording (performance) copyright (℗), if any.
+    pub fn phonographic_copyright_text(&self) -> Option<&str> {
+        copyright_text(&self.copyrights, CopyrightType::Performance)
+    }
+
+    /// The album's [Universal Product Code](https://en.wikipedia.org/wiki/Universal_Product_Code), if known.
+    pub fn upc(&self) -> Option<&str> {
+        self.external_ids.upc.as_deref()
+    }
+
+    /// The album's [International Article Number](https://en.wikipedia.org/wiki/International_Article_Number), if known.
+    pub fn ean(&self) -> Option<&str> {
+        self.external_ids.ean.as_deref()
+    }
+
+    /// Returns `true` if the album is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+
+    /// The release date, parsed according to its precision.
+    ///
+    /// Returns `None` if the date couldn't be parsed.
+    pub fn release_date(&self) -> Option<ReleaseDate> {
+        ReleaseDate::parse(&self.release_date, &self.release_date_precision)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedAlbum {
     pub album_type: AlbumType,
     pub total_tracks: u32,
@@ -48,7 +95,22 @@ pub struct SimplifiedAlbum {
     pub artists: Vec<SimplifiedArtist>,
 }
 
+impl SimplifiedAlbum {
+    /// Returns `true` if the album is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+
+    /// The release date, parsed according to its precision.
+    ///
+    /// Returns `None` if the date couldn't be parsed.
+    pub fn release_date(&self) -> Option<ReleaseDate> {
+        ReleaseDate::parse(&self.release_date, &self.release_date_precision)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SavedAlbum {
     pub added_at: DateTime<Utc>,
     pub album: Album,
@@ -64,7 +126,7 @@ pub(crate) struct PagedAlbums {
     pub(crate) albums: Page<SimplifiedAlbum>,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AlbumType {
     #[serde(alias = "ALBUM")]
@@ -73,15 +135,22 @@ pub enum AlbumType {
     Single,
     #[serde(alias = "COMPILATION")]
     Compilation,
+    #[serde(other)]
+    Unknown,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// The relationship between an artist and an album.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum AlbumGroup {
     Album,
     Single,
     Compilation,
+    /// The artist appears on the album, but it's not one of their own releases.
     AppearsOn,
+    /// A group this library doesn't know about yet. It's ignored when used in requests.
+    #[serde(other)]
+    Unknown,
 }
 
 impl AsRef<str> for AlbumGroup {
@@ -91,6 +160,7 @@ impl AsRef<str> for AlbumGroup {
             AlbumGroup::Single => "single",
             AlbumGroup::Compilation => "compilation",
             AlbumGroup::AppearsOn => "appears_on",
+            AlbumGroup::Unknown => "unknown",
         }
     }
 }
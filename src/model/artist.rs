@@ -3,6 +3,7 @@ use serde::Deserialize;
 use super::*;
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Artist {
     pub external_urls: ExternalUrls,
     pub followers: Followers,
@@ -16,7 +17,15 @@ pub struct Artist {
     pub uri: String,
 }
 
+impl Artist {
+    /// The total number of followers.
+    pub fn follower_count(&self) -> u32 {
+        self.followers.total
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedArtist {
     pub external_urls: ExternalUrls,
     pub href: String,
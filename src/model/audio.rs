@@ -1,7 +1,10 @@
+use std::{fmt::Display, time::Duration};
+
 use serde::Deserialize;
 use serde_repr::*;
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct AudioFeatures {
     pub acousticness: f32,
     pub analysis_url: String,
@@ -25,12 +28,31 @@ pub struct AudioFeatures {
     pub valence: f32,
 }
 
+impl AudioFeatures {
+    /// The key the track is in, or `None` if no key was detected.
+    pub fn key(&self) -> Option<Pitch> {
+        Pitch::from_pitch_class(self.key)
+    }
+
+    /// The key and mode the track is in (e.g. C# minor), or `None` if no key was detected.
+    pub fn musical_key(&self) -> Option<Key> {
+        Key::new(self.key, self.mode)
+    }
+
+    /// The duration of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+}
+
+#[cfg(feature = "deprecated-endpoints")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct AudioFeaturesResult {
-    pub(crate) audio_features: Vec<AudioFeatures>,
+    pub(crate) audio_features: Vec<Option<AudioFeatures>>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct AudioAnalysis {
     pub meta: Meta,
     pub track: TrackAnalysis,
@@ -41,7 +63,68 @@ pub struct AudioAnalysis {
     pub tatums: Vec<Tatum>,
 }
 
+impl AudioAnalysis {
+    /// The section playing at the given time (in seconds), if any.
+    pub fn section_at(&self, seconds: f32) -> Option<&Section> {
+        interval_at(&self.sections, seconds)
+    }
+
+    /// The segment playing at the given time (in seconds), if any.
+    pub fn segment_at(&self, seconds: f32) -> Option<&Segment> {
+        interval_at(&self.segments, seconds)
+    }
+
+    /// The bars starting between `start` (inclusive) and `end` (exclusive), in seconds.
+    pub fn bars_between(&self, start: f32, end: f32) -> impl Iterator<Item = &Bar> {
+        intervals_between(&self.bars, start, end).iter()
+    }
+
+    /// The beats starting between `start` (inclusive) and `end` (exclusive), in seconds.
+    pub fn beats_between(&self, start: f32, end: f32) -> impl Iterator<Item = &Beat> {
+        intervals_between(&self.beats, start, end).iter()
+    }
+}
+
+// The analysis intervals are sorted by their start time, so they can be binary searched.
+trait Interval {
+    fn start(&self) -> f32;
+    fn duration(&self) -> f32;
+}
+
+macro_rules! impl_interval {
+    ($($t:ty),+) => {
+        $(
+            impl Interval for $t {
+                fn start(&self) -> f32 {
+                    self.start
+                }
+
+                fn duration(&self) -> f32 {
+                    self.duration
+                }
+            }
+        )+
+    };
+}
+
+impl_interval!(Bar, Beat, Section, Segment);
+
+fn interval_at<T: Interval>(intervals: &[T], seconds: f32) -> Option<&T> {
+    let index = intervals.partition_point(|i| i.start() <= seconds);
+    let interval = intervals.get(index.checked_sub(1)?)?;
+
+    (seconds < interval.start() + interval.duration()).then_some(interval)
+}
+
+fn intervals_between<T: Interval>(intervals: &[T], start: f32, end: f32) -> &[T] {
+    let from = intervals.partition_point(|i| i.start() < start);
+    let to = intervals.partition_point(|i| i.start() < end).max(from);
+
+    &intervals[from..to]
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Meta {
     pub analyzer_version: String,
     pub platform: String,
@@ -54,6 +137,7 @@ pub struct Meta {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct TrackAnalysis {
     pub num_samples: u32,
     pub duration: f32,
@@ -84,7 +168,20 @@ pub struct TrackAnalysis {
     pub rhythm_version: f32,
 }
 
+impl TrackAnalysis {
+    /// The estimated key of the track, or `None` if no key was detected.
+    pub fn key(&self) -> Option<Pitch> {
+        Pitch::from_pitch_class(self.key)
+    }
+
+    /// The estimated key and mode of the track, or `None` if no key was detected.
+    pub fn musical_key(&self) -> Option<Key> {
+        Key::new(self.key, self.mode)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Bar {
     pub start: f32,
     pub duration: f32,
@@ -92,6 +189,7 @@ pub struct Bar {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Beat {
     pub start: f32,
     pub duration: f32,
@@ -99,6 +197,7 @@ pub struct Beat {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Section {
     pub start: f32,
     pub duration: f32,
@@ -114,7 +213,20 @@ pub struct Section {
     pub time_signature_confidence: f32,
 }
 
+impl Section {
+    /// The estimated key of the section, or `None` if no key was detected.
+    pub fn key(&self) -> Option<Pitch> {
+        Pitch::from_pitch_class(self.key)
+    }
+
+    /// The estimated key and mode of the section, or `None` if no key was detected.
+    pub fn musical_key(&self) -> Option<Key> {
+        Key::new(self.key, self.mode)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Segment {
     pub start: f32,
     pub duration: f32,
@@ -128,15 +240,108 @@ pub struct Segment {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Tatum {
     pub start: f32,
     pub duration: f32,
     pub confidence: f32,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize_repr)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize_repr)]
 #[repr(u8)]
 pub enum Mode {
     Minor,
     Major,
 }
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Mode::Minor => "minor",
+            Mode::Major => "major",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// A pitch class, using standard [Pitch Class notation](https://en.wikipedia.org/wiki/Pitch_class).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Pitch {
+    C,
+    CSharp,
+    D,
+    DSharp,
+    E,
+    F,
+    FSharp,
+    G,
+    GSharp,
+    A,
+    ASharp,
+    B,
+}
+
+impl Pitch {
+    /// Get the pitch from its pitch class (0 = C, 1 = C♯/D♭, 2 = D and so on).
+    /// Returns `None` if it's outside of the `0 - 11` range (Spotify uses -1 for no key).
+    pub fn from_pitch_class(pitch_class: i32) -> Option<Self> {
+        let pitch = match pitch_class {
+            0 => Self::C,
+            1 => Self::CSharp,
+            2 => Self::D,
+            3 => Self::DSharp,
+            4 => Self::E,
+            5 => Self::F,
+            6 => Self::FSharp,
+            7 => Self::G,
+            8 => Self::GSharp,
+            9 => Self::A,
+            10 => Self::ASharp,
+            11 => Self::B,
+            _ => return None,
+        };
+
+        Some(pitch)
+    }
+}
+
+impl Display for Pitch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Pitch::C => "C",
+            Pitch::CSharp => "C#",
+            Pitch::D => "D",
+            Pitch::DSharp => "D#",
+            Pitch::E => "E",
+            Pitch::F => "F",
+            Pitch::FSharp => "F#",
+            Pitch::G => "G",
+            Pitch::GSharp => "G#",
+            Pitch::A => "A",
+            Pitch::ASharp => "A#",
+            Pitch::B => "B",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// A musical key, which is displayed as e.g. "C# minor".
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    pub pitch: Pitch,
+    pub mode: Mode,
+}
+
+impl Key {
+    fn new(pitch_class: i32, mode: Mode) -> Option<Self> {
+        Pitch::from_pitch_class(pitch_class).map(|pitch| Self { pitch, mode })
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.pitch, self.mode)
+    }
+}
@@ -1,8 +1,11 @@
+use std::time::Duration;
+
 use serde::Deserialize;
 
 use super::*;
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Audiobook {
     pub authors: Vec<Author>,
     #[serde(default)]
@@ -27,7 +30,31 @@ pub struct Audiobook {
     pub chapters: Page<SimplifiedChapter>,
 }
 
+impl Audiobook {
+    /// The total number of chapters in the audiobook, not just the ones on the embedded page.
+    pub fn chapter_count(&self) -> u32 {
+        self.total_chapters
+    }
+
+    /// Get all of the audiobook's chapters, using the embedded page and fetching the remaining pages.
+    pub async fn all_chapters<F: AuthFlow, V: Verifier>(
+        &self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Vec<SimplifiedChapter>> {
+        let mut chapters = self.chapters.items.clone();
+        chapters.extend(self.chapters.get_remaining(spotify).await?);
+
+        Ok(chapters)
+    }
+
+    /// Returns `true` if the audiobook is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedAudiobook {
     pub authors: Vec<Author>,
     #[serde(default)]
@@ -51,12 +78,20 @@ pub struct SimplifiedAudiobook {
     pub total_chapters: Option<u32>,
 }
 
+impl SimplifiedAudiobook {
+    /// Returns `true` if the audiobook is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Audiobooks {
     pub(crate) audiobooks: Vec<Audiobook>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Chapter {
     pub audio_preview_url: Option<String>,
     #[serde(default)]
@@ -82,7 +117,27 @@ pub struct Chapter {
     pub audiobook: SimplifiedAudiobook,
 }
 
+impl Chapter {
+    /// Returns `true` if the chapter is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+
+    /// The duration of the chapter.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+
+    /// The release date, parsed according to its precision.
+    ///
+    /// Returns `None` if the date couldn't be parsed.
+    pub fn release_date(&self) -> Option<ReleaseDate> {
+        ReleaseDate::parse(&self.release_date, &self.release_date_precision)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedChapter {
     pub audio_preview_url: Option<String>,
     #[serde(default)]
@@ -107,17 +162,38 @@ pub struct SimplifiedChapter {
     pub restrictions: Option<Restrictions>,
 }
 
+impl SimplifiedChapter {
+    /// Returns `true` if the chapter is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+
+    /// The duration of the chapter.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+
+    /// The release date, parsed according to its precision.
+    ///
+    /// Returns `None` if the date couldn't be parsed.
+    pub fn release_date(&self) -> Option<ReleaseDate> {
+        ReleaseDate::parse(&self.release_date, &self.release_date_precision)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Chapters {
-    pub(crate) chapters: Vec<Chapter>,
+    pub(crate) chapters: Vec<Option<Chapter>>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Author {
     pub name: String,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Narrator {
     pub name: String,
 }
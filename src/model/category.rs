@@ -3,6 +3,7 @@ use serde::Deserialize;
 use super::{Image, Page};
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Category {
     pub href: String,
     pub icons: Vec<Image>,
@@ -10,6 +11,23 @@ pub struct Category {
     pub name: String,
 }
 
+impl Category {
+    /// The largest icon of the category.
+    pub fn largest_icon(&self) -> Option<&Image> {
+        Image::largest(&self.icons)
+    }
+
+    /// The smallest icon that's at least `size` pixels wide and tall, falling back to the largest icon
+    /// if none are big enough (or their dimensions are unknown).
+    pub fn icon_at_least(&self, size: u32) -> Option<&Image> {
+        self.icons
+            .iter()
+            .filter(|i| i.width.unwrap_or(0) >= size && i.height.unwrap_or(0) >= size)
+            .min_by_key(|i| i.width.unwrap_or(0))
+            .or_else(|| self.largest_icon())
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Categories {
     pub(crate) categories: Page<Category>,
@@ -1,6 +1,181 @@
+use std::{fmt::Display, str::FromStr};
+
 use serde::Deserialize;
+use thiserror::Error;
 
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Markets {
     pub(crate) markets: Vec<String>,
 }
+
+/// An [ISO 3166-1 alpha-2](https://en.wikipedia.org/wiki/ISO_3166-1_alpha-2) country code (e.g. `SE`).
+///
+/// It can be passed anywhere a country is expected, as it converts into a `String`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Country(String);
+
+impl Country {
+    /// Create a country from its code, which must be two uppercase ASCII letters.
+    ///
+    /// Note that this only checks the format of the code, not whether the country exists.
+    pub fn new(code: &str) -> Result<Self, ParseCountryError> {
+        if code.len() == 2 && code.chars().all(|c| c.is_ascii_uppercase()) {
+            Ok(Self(code.to_owned()))
+        } else {
+            Err(ParseCountryError(code.to_owned()))
+        }
+    }
+
+    /// A best-effort guess of the user's country, based on the region of the
+    /// operating system's locale (e.g. `SE` for `sv-SE`).
+    ///
+    /// Returns `None` if the locale can't be determined or doesn't include a region.
+    #[cfg(feature = "system-locale")]
+    pub fn from_system_locale() -> Option<Self> {
+        sys_locale::get_locale().and_then(|locale| Self::from_locale_tag(&locale))
+    }
+
+    /// The market to use by default: the country of the operating system's locale, or
+    /// `from_token` (i.e. the country associated with the user's access token) if it
+    /// can't be determined.
+    #[cfg(feature = "system-locale")]
+    pub fn system_market() -> String {
+        Self::from_system_locale()
+            .map(String::from)
+            .unwrap_or_else(|| "from_token".to_owned())
+    }
+
+    /// Find the region in a locale tag such as `en-US`, `zh-Hans-CN` or `sv_SE.UTF-8`.
+    #[cfg(feature = "system-locale")]
+    fn from_locale_tag(tag: &str) -> Option<Self> {
+        let tag = tag.split(['.', '@']).next()?;
+
+        tag.split(['-', '_'])
+            .skip(1)
+            .find(|subtag| subtag.len() == 2)
+            .and_then(|region| Self::new(&region.to_ascii_uppercase()).ok())
+    }
+}
+
+impl AsRef<str> for Country {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for Country {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Country {
+    type Err = ParseCountryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl From<Country> for String {
+    fn from(value: Country) -> Self {
+        value.0
+    }
+}
+
+/// The error returned when a string isn't a valid [`Country`] code.
+#[derive(Clone, Debug, Error)]
+#[error("\"{0}\" is not a valid ISO 3166-1 alpha-2 country code")]
+pub struct ParseCountryError(pub String);
+
+/// A locale, made of an [ISO 639-1](https://en.wikipedia.org/wiki/ISO_639-1) language code
+/// and a [`Country`], which is displayed as e.g. `es_MX`.
+///
+/// It can be passed anywhere a locale is expected, as it converts into a `String`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Locale {
+    language: String,
+    country: Country,
+}
+
+impl Locale {
+    /// Create a locale from a language code, which must be two ASCII letters
+    /// (the case doesn't matter), and a country.
+    pub fn new(language: &str, country: Country) -> Result<Self, ParseLocaleError> {
+        if language.len() == 2 && language.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(Self {
+                language: language.to_ascii_lowercase(),
+                country,
+            })
+        } else {
+            Err(ParseLocaleError(format!("{language}_{country}")))
+        }
+    }
+
+    /// The language code (e.g. `es`).
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// The country (e.g. `MX`).
+    pub fn country(&self) -> &Country {
+        &self.country
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}_{}", self.language, self.country)
+    }
+}
+
+impl FromStr for Locale {
+    type Err = ParseLocaleError;
+
+    /// Parse a locale such as `es_MX` (`es-MX` is accepted as well).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (language, country) = s
+            .split_once(['_', '-'])
+            .ok_or_else(|| ParseLocaleError(s.to_owned()))?;
+        let country = Country::new(country).map_err(|_| ParseLocaleError(s.to_owned()))?;
+
+        Self::new(language, country).map_err(|_| ParseLocaleError(s.to_owned()))
+    }
+}
+
+impl From<Locale> for String {
+    fn from(value: Locale) -> Self {
+        value.to_string()
+    }
+}
+
+/// The error returned when a string isn't a valid [`Locale`].
+#[derive(Clone, Debug, Error)]
+#[error("\"{0}\" is not a valid locale (expected e.g. \"es_MX\")")]
+pub struct ParseLocaleError(pub String);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn country_codes() {
+        assert_eq!(Country::new("SE").unwrap().as_ref(), "SE");
+        assert!(Country::new("se").is_err());
+        assert!(Country::new("en_US").is_err());
+        assert!(Country::new("").is_err());
+    }
+
+    #[test]
+    fn locales() {
+        let locale = Locale::new("sv", Country::new("SE").unwrap()).unwrap();
+
+        assert_eq!(locale.to_string(), "sv_SE");
+        assert_eq!("sv_SE".parse::<Locale>().unwrap(), locale);
+        assert_eq!("sv-SE".parse::<Locale>().unwrap(), locale);
+        assert!("sv_se".parse::<Locale>().is_err());
+        assert!("sv".parse::<Locale>().is_err());
+        assert!("".parse::<Locale>().is_err());
+        assert!(Locale::new("", Country::new("SE").unwrap()).is_err());
+    }
+}
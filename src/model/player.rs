@@ -1,9 +1,13 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use super::{track::Track, *};
+use crate::endpoint::player::RepeatMode;
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct PlaybackState {
     pub device: Option<Device>,
     pub repeat_state: Option<RepeatState>,
@@ -17,15 +21,106 @@ pub struct PlaybackState {
     pub actions: Actions,
 }
 
+impl PlaybackState {
+    /// How far the progress can drift from the expected position before it's considered a seek.
+    const SEEK_TOLERANCE_MS: i64 = 2000;
+
+    /// The progress into the currently playing item, if known.
+    pub fn progress(&self) -> Option<Duration> {
+        self.progress_ms
+            .map(|progress| Duration::from_millis(progress.into()))
+    }
+
+    /// The currently playing item, if any.
+    pub fn item(&self) -> Option<&PlayableItem> {
+        self.item.as_ref()
+    }
+
+    /// The current repeat mode, which is `None` if it's unknown.
+    pub fn repeat_mode(&self) -> Option<RepeatMode> {
+        match self.repeat_state? {
+            RepeatState::Off => Some(RepeatMode::Off),
+            RepeatState::Track => Some(RepeatMode::Track),
+            RepeatState::Context => Some(RepeatMode::Context),
+            RepeatState::Unknown => None,
+        }
+    }
+
+    /// How much of the currently playing item has been played, as a percentage from 0 to 100.
+    ///
+    /// Returns `None` if the progress or the item is unknown.
+    pub fn percent_complete(&self) -> Option<f32> {
+        let duration = self.item()?.duration().as_secs_f32();
+        let progress = self.progress()?.as_secs_f32();
+
+        (duration > 0.0).then(|| (progress / duration * 100.0).min(100.0))
+    }
+
+    /// Returns `true` if the playing item, the playing state or the position (e.g. after seeking)
+    /// differ from the `previous` state, given the time elapsed since then.
+    pub(crate) fn changed_since(&self, previous: &PlaybackState, elapsed: Duration) -> bool {
+        if self.item.as_ref().map(PlayableItem::uri)
+            != previous.item.as_ref().map(PlayableItem::uri)
+            || self.is_playing != previous.is_playing
+        {
+            return true;
+        }
+
+        match (self.progress_ms, previous.progress_ms) {
+            (Some(progress), Some(previous_progress)) => {
+                let mut expected = i64::from(previous_progress);
+
+                if previous.is_playing {
+                    expected += i64::try_from(elapsed.as_millis()).unwrap_or(i64::MAX);
+                }
+
+                (i64::from(progress) - expected).abs() > Self::SEEK_TOLERANCE_MS
+            }
+            (progress, previous_progress) => progress != previous_progress,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Device {
     pub id: Option<String>,
     pub is_active: bool,
     pub is_private_session: bool,
     pub is_restricted: bool,
     pub name: String,
-    pub r#type: String,
+    pub r#type: DeviceType,
+    /// The volume, from 0 to 100. It's `None` if the device doesn't report it.
     pub volume_percent: Option<u32>,
+    /// Whether the volume of the device can be changed, e.g. to disable a volume slider.
+    #[serde(default)]
+    pub supports_volume: bool,
+}
+
+/// The kind of device playback can happen on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+pub enum DeviceType {
+    Computer,
+    Tablet,
+    Smartphone,
+    Speaker,
+    #[serde(rename = "TV")]
+    Tv,
+    /// An audio/video receiver.
+    #[serde(rename = "AVR")]
+    Avr,
+    /// A set-top box.
+    #[serde(rename = "STB")]
+    Stb,
+    AudioDongle,
+    GameConsole,
+    /// A Chromecast (or other Cast) device with a screen.
+    CastVideo,
+    /// A Chromecast (or other Cast) audio-only device.
+    CastAudio,
+    Automobile,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -33,21 +128,39 @@ pub(crate) struct Devices {
     pub(crate) devices: Vec<Device>,
 }
 
+/// The context an item is played from, e.g. an album or a playlist.
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Context {
-    pub r#type: String,
+    pub r#type: ContextType,
     pub href: String,
     pub external_urls: ExternalUrls,
     pub uri: String,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextType {
+    Album,
+    Artist,
+    Playlist,
+    Show,
+    Audiobook,
+    /// The user's library, e.g. their liked songs.
+    Collection,
+    #[serde(other)]
+    Unknown,
+}
+
 /// Allows to update the user interface based on which playback actions are available within the current context.
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Actions {
     pub disallows: Disallows,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Disallows {
     pub interrupting_playback: Option<bool>,
     pub pausing: Option<bool>,
@@ -62,19 +175,36 @@ pub struct Disallows {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct PlayHistory {
     pub track: Track,
+    /// The UTC date and time the track was played at.
     pub played_at: DateTime<Utc>,
+    /// The context the track was played from, which is `None` if it wasn't played from one.
     pub context: Option<Context>,
 }
 
+impl PlayHistory {
+    /// The track that was played.
+    pub fn track(&self) -> &Track {
+        &self.track
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Queue {
+    /// The item that's currently playing, which is `None` if nothing is playing
+    /// (or the item couldn't be parsed).
+    #[serde(default, deserialize_with = "deserialize_invalid_as_none")]
     pub currently_playing: Option<PlayableItem>,
+    /// The upcoming items. Items that couldn't be parsed are skipped.
+    #[serde(deserialize_with = "deserialize_skip_invalid")]
     pub queue: Vec<PlayableItem>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct CurrentlyPlayingTrack {
     pub context: Option<Context>,
     pub timestamp: u64,
@@ -85,12 +215,14 @@ pub struct CurrentlyPlayingTrack {
     pub actions: Actions,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RepeatState {
     Off,
     Track,
     Context,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Clone, Copy, Debug, Deserialize)]
@@ -99,5 +231,6 @@ pub enum CurrentlyPlayingType {
     Track,
     Episode,
     Ad,
+    #[serde(other)]
     Unknown,
 }
@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
 
 use super::{user::ReferenceUser, *};
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Playlist {
     pub collaborative: bool,
     pub description: Option<String>,
@@ -11,28 +14,52 @@ pub struct Playlist {
     pub followers: Followers,
     pub href: String,
     pub id: String,
+    /// The playlist's cover images, which is empty if Spotify returns `null` (e.g. for some empty playlists).
+    #[serde(default, deserialize_with = "deserialize_null_as_default")]
     pub images: Vec<Image>,
     pub name: String,
     pub owner: ReferenceUser,
     pub public: Option<bool>,
-    pub snapshot_id: String,
+    pub snapshot_id: SnapshotId,
     pub tracks: Page<PlaylistTrack>,
     pub r#type: String,
     pub uri: String,
 }
 
+impl Playlist {
+    /// The total number of followers.
+    pub fn follower_count(&self) -> u32 {
+        self.followers.total
+    }
+
+    /// The total number of items in the playlist, not just the ones on the embedded page.
+    pub fn track_count(&self) -> u32 {
+        self.tracks.total
+    }
+
+    /// Returns `true` if the playlist is owned by the user with the given ID.
+    ///
+    /// Note that collaborative playlists can also be edited by users other than the owner.
+    pub fn is_owned_by(&self, user_id: &str) -> bool {
+        self.owner.id == user_id
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedPlaylist {
     pub collaborative: bool,
     pub description: Option<String>,
     pub external_urls: ExternalUrls,
     pub href: String,
     pub id: String,
+    /// The playlist's cover images, which is empty if Spotify returns `null` (e.g. for some empty playlists).
+    #[serde(default, deserialize_with = "deserialize_null_as_default")]
     pub images: Vec<Image>,
     pub name: String,
     pub owner: ReferenceUser,
     pub public: Option<bool>,
-    pub snapshot_id: String,
+    pub snapshot_id: SnapshotId,
     /// A collection containing a link (`href`) to the Web API endpoint where full details of the playlist's tracks can be retrieved,
     /// along with the total number of tracks in the playlist. Note, a track object may be `null`. This can happen if a track is no longer available.
     pub tracks: Option<TrackReference>,
@@ -40,22 +67,42 @@ pub struct SimplifiedPlaylist {
     pub uri: String,
 }
 
+impl SimplifiedPlaylist {
+    /// The total number of items in the playlist, which is 0 if Spotify didn't return it.
+    pub fn track_count(&self) -> u32 {
+        self.tracks.as_ref().map_or(0, |tracks| tracks.total)
+    }
+
+    /// Returns `true` if the playlist is owned by the user with the given ID.
+    ///
+    /// Note that collaborative playlists can also be edited by users other than the owner.
+    pub fn is_owned_by(&self, user_id: &str) -> bool {
+        self.owner.id == user_id
+    }
+}
+
+#[cfg(feature = "deprecated-endpoints")]
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Playlists {
     pub(crate) playlists: Page<SimplifiedPlaylist>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct PlaylistTrack {
     /// The date and time the track or episode was added. Note: some very old playlists may return null in this field.
     pub added_at: Option<DateTime<Utc>>,
     /// The Spotify user who added the track or episode. Note: some very old playlists may return null in this field.
     pub added_by: Option<ReferenceUser>,
+    /// Whether the item is a local file, in which case `track` will be a [`PlayableItem::Local`].
     pub is_local: bool,
-    pub track: PlayableItem,
+    pub primary_color: Option<String>,
+    /// The track or episode. This may be `None` if the item is no longer available.
+    pub track: Option<PlayableItem>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct FeaturedPlaylists {
     pub message: String,
     pub playlists: Page<SimplifiedPlaylist>,
@@ -63,13 +110,47 @@ pub struct FeaturedPlaylists {
 
 /// A collection containing a link (`href`) to the Web API endpoint where full details of the playlist's tracks can be retrieved,
 /// along with the total number of tracks in the playlist. Note, a track object may be `null`. This can happen if a track is no longer available.
+///
+/// Any other fields are ignored, so a full page of tracks deserializes into this as well.
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct TrackReference {
     pub href: String,
     pub total: u32,
 }
 
+/// The ID of a playlist's snapshot (version), which is returned every time the playlist is changed.
+///
+/// It can be passed back when changing the playlist, so that the changes are made against that version.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SnapshotId(pub String);
+
+impl AsRef<str> for SnapshotId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for SnapshotId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<String> for SnapshotId {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SnapshotId {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
-pub(crate) struct SnapshotId {
-    pub(crate) snapshot_id: String,
+pub(crate) struct Snapshot {
+    pub(crate) snapshot_id: SnapshotId,
 }
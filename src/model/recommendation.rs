@@ -3,20 +3,54 @@ use serde::Deserialize;
 use super::track::Track;
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Recommendations {
     pub seeds: Vec<RecommendationSeed>,
     pub tracks: Vec<Track>,
 }
 
+impl Recommendations {
+    /// The recommended tracks.
+    pub fn tracks(&self) -> &[Track] {
+        &self.tracks
+    }
+
+    /// The seeds used for the recommendations, along with how many tracks each of them
+    /// contributed at each stage.
+    pub fn seeds(&self) -> &[RecommendationSeed] {
+        &self.seeds
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[non_exhaustive]
 pub struct RecommendationSeed {
+    /// The number of tracks available after min\_\*, max\_\* and target\_\* attributes have been applied.
     pub after_filtering_size: u32,
+    /// The number of tracks available after relinking for regional availability.
     pub after_relinking_size: u32,
-    pub href: String,
+    /// A link to the full track or artist data for this seed. This is `None` for genre seeds.
+    pub href: Option<String>,
+    /// The id used to select this seed, which is the genre name for genre seeds.
     pub id: String,
+    /// The number of recommended tracks available for this seed.
     pub initial_pool_size: u32,
-    pub r#type: String,
+    pub r#type: RecommendationSeedType,
+}
+
+/// The kind of a [`RecommendationSeed`].
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RecommendationSeedType {
+    #[serde(alias = "artist")]
+    Artist,
+    #[serde(alias = "track")]
+    Track,
+    #[serde(alias = "genre")]
+    Genre,
+    #[serde(other)]
+    Unknown,
 }
 
 #[derive(Clone, Debug, Deserialize)]
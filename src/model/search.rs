@@ -1,4 +1,7 @@
+use std::{fmt::Display, str::FromStr};
+
 use serde::Deserialize;
+use thiserror::Error;
 
 use super::{
     album::SimplifiedAlbum,
@@ -10,7 +13,12 @@ use super::{
     Page,
 };
 
+/// The results of a search, which has a page for each requested item type.
+///
+/// The page of an item type that wasn't requested is `None`, while the page of
+/// an item type without any results is empty (with a `total` of `0`).
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SearchResults {
     pub tracks: Option<Page<Track>>,
     pub artists: Option<Page<Artist>>,
@@ -21,7 +29,30 @@ pub struct SearchResults {
     pub audiobooks: Option<Page<SimplifiedAudiobook>>,
 }
 
-#[derive(Clone, Debug)]
+impl SearchResults {
+    /// The total number of results for the item type, or `None` if it wasn't requested.
+    pub fn total_for(&self, item: Item) -> Option<u32> {
+        match item {
+            Item::Album => self.albums.as_ref().map(|p| p.total),
+            Item::Artist => self.artists.as_ref().map(|p| p.total),
+            Item::Playlist => self.playlists.as_ref().map(|p| p.total),
+            Item::Track => self.tracks.as_ref().map(|p| p.total),
+            Item::Show => self.shows.as_ref().map(|p| p.total),
+            Item::Episode => self.episodes.as_ref().map(|p| p.total),
+            Item::Audiobook => self.audiobooks.as_ref().map(|p| p.total),
+        }
+    }
+
+    /// Returns `true` if there are no results for any of the requested item types.
+    pub fn is_empty(&self) -> bool {
+        Item::all()
+            .iter()
+            .all(|item| self.total_for(*item).unwrap_or(0) == 0)
+    }
+}
+
+/// A type of item that can be searched for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Item {
     Album,
     Artist,
@@ -59,3 +90,27 @@ impl AsRef<str> for Item {
         }
     }
 }
+
+impl Display for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_ref())
+    }
+}
+
+impl FromStr for Item {
+    type Err = ParseItemError;
+
+    /// Parse an item type from its name (e.g. `album`), ignoring the case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::all()
+            .iter()
+            .find(|i| i.as_ref().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or_else(|| ParseItemError(s.to_owned()))
+    }
+}
+
+/// The error returned when a string isn't a valid search [`Item`] type.
+#[derive(Clone, Debug, Error)]
+#[error("\"{0}\" is not a valid search item type")]
+pub struct ParseItemError(pub String);
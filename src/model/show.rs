@@ -1,9 +1,12 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use super::*;
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Show {
     #[serde(default)]
     pub available_markets: Vec<String>,
@@ -26,7 +29,31 @@ pub struct Show {
     pub episodes: Page<SimplifiedEpisode>,
 }
 
+impl Show {
+    /// The total number of episodes in the show, not just the ones on the embedded page.
+    pub fn episode_count(&self) -> u32 {
+        self.total_episodes
+    }
+
+    /// Get all of the show's episodes, using the embedded page and fetching the remaining pages.
+    pub async fn all_episodes<F: AuthFlow, V: Verifier>(
+        &self,
+        spotify: &Client<Token, F, V>,
+    ) -> Result<Vec<SimplifiedEpisode>> {
+        let mut episodes = self.episodes.items.clone();
+        episodes.extend(self.episodes.get_remaining(spotify).await?);
+
+        Ok(episodes)
+    }
+
+    /// Returns `true` if the show is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedShow {
     #[serde(default)]
     pub available_markets: Vec<String>,
@@ -48,7 +75,15 @@ pub struct SimplifiedShow {
     pub total_episodes: u32,
 }
 
+impl SimplifiedShow {
+    /// Returns `true` if the show is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(&self.available_markets, market)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SavedShow {
     pub added_at: DateTime<Utc>,
     pub show: SimplifiedShow,
@@ -60,6 +95,7 @@ pub(crate) struct Shows {
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Episode {
     pub audio_preview_url: Option<String>,
     pub description: String,
@@ -70,8 +106,11 @@ pub struct Episode {
     pub href: String,
     pub id: String,
     pub images: Vec<Image>,
+    /// Whether the episode is hosted outside of Spotify's CDN.
     pub is_externally_hosted: bool,
-    pub is_playable: bool,
+    /// Whether the episode is playable in the given market. This is `None` if it's unknown
+    /// (e.g. because no market was specified in the request).
+    pub is_playable: Option<bool>,
     pub languages: Vec<String>,
     pub name: String,
     pub release_date: String,
@@ -83,7 +122,35 @@ pub struct Episode {
     pub show: SimplifiedShow,
 }
 
+impl Episode {
+    /// Returns `true` if the episode's show is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        self.show.is_available_in(market)
+    }
+
+    /// Returns `true` if the episode is playable and its show is available in the given market
+    /// (an ISO 3166-1 alpha-2 country code).
+    ///
+    /// The episode is assumed to be playable unless Spotify explicitly marked it as unplayable.
+    pub fn is_playable_in(&self, market: &str) -> bool {
+        self.is_playable != Some(false) && self.is_available_in(market)
+    }
+
+    /// The duration of the episode.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+
+    /// The release date, parsed according to its precision.
+    ///
+    /// Returns `None` if the date couldn't be parsed.
+    pub fn release_date(&self) -> Option<ReleaseDate> {
+        ReleaseDate::parse(&self.release_date, &self.release_date_precision)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedEpisode {
     pub audio_preview_url: Option<String>,
     pub description: String,
@@ -94,8 +161,11 @@ pub struct SimplifiedEpisode {
     pub href: String,
     pub id: String,
     pub images: Vec<Image>,
+    /// Whether the episode is hosted outside of Spotify's CDN.
     pub is_externally_hosted: bool,
-    pub is_playable: bool,
+    /// Whether the episode is playable in the given market. This is `None` if it's unknown
+    /// (e.g. because no market was specified in the request).
+    pub is_playable: Option<bool>,
     pub languages: Vec<String>,
     pub name: String,
     pub release_date: String,
@@ -106,7 +176,22 @@ pub struct SimplifiedEpisode {
     pub restrictions: Option<Restrictions>,
 }
 
+impl SimplifiedEpisode {
+    /// The duration of the episode.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+
+    /// The release date, parsed according to its precision.
+    ///
+    /// Returns `None` if the date couldn't be parsed.
+    pub fn release_date(&self) -> Option<ReleaseDate> {
+        ReleaseDate::parse(&self.release_date, &self.release_date_precision)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SavedEpisode {
     pub added_at: DateTime<Utc>,
     pub episode: Episode,
@@ -114,5 +199,5 @@ pub struct SavedEpisode {
 
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Episodes {
-    pub(crate) episodes: Vec<Episode>,
+    pub(crate) episodes: Vec<Option<Episode>>,
 }
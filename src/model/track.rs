@@ -1,9 +1,12 @@
+use std::time::Duration;
+
 use chrono::{DateTime, Utc};
 use serde::Deserialize;
 
 use super::{album::SimplifiedAlbum, artist::SimplifiedArtist, *};
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct Track {
     pub album: SimplifiedAlbum,
     pub artists: Vec<SimplifiedArtist>,
@@ -16,6 +19,9 @@ pub struct Track {
     pub href: String,
     pub id: String,
     pub is_playable: Option<bool>,
+    /// Set if [track relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
+    /// was applied (which only happens when a market is supplied), in which case it contains
+    /// the track that was originally requested.
     pub linked_from: Option<LinkedFrom>,
     pub restrictions: Option<Restrictions>,
     pub name: String,
@@ -27,12 +33,39 @@ pub struct Track {
     pub is_local: bool,
 }
 
+impl Track {
+    /// The track's [International Standard Recording Code](https://en.wikipedia.org/wiki/International_Standard_Recording_Code), if known.
+    pub fn isrc(&self) -> Option<&str> {
+        self.external_ids.isrc.as_deref()
+    }
+
+    /// Returns `true` if the track is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(
+            self.available_markets.as_deref().unwrap_or_default(),
+            market,
+        )
+    }
+
+    /// The ID of the track that was originally requested, which differs from [`id`](Self::id)
+    /// if the track was relinked.
+    pub fn requested_id(&self) -> &str {
+        self.linked_from.as_ref().map_or(&self.id, |l| &l.id)
+    }
+
+    /// The duration of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Tracks {
     pub(crate) tracks: Vec<Track>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SimplifiedTrack {
     pub artists: Vec<SimplifiedArtist>,
     pub available_markets: Option<Vec<String>>,
@@ -43,6 +76,9 @@ pub struct SimplifiedTrack {
     pub href: String,
     pub id: String,
     pub is_playable: Option<bool>,
+    /// Set if [track relinking](https://developer.spotify.com/documentation/web-api/concepts/track-relinking)
+    /// was applied (which only happens when a market is supplied), in which case it contains
+    /// the track that was originally requested.
     pub linked_from: Option<LinkedFrom>,
     pub restrictions: Option<Restrictions>,
     pub name: String,
@@ -53,13 +89,36 @@ pub struct SimplifiedTrack {
     pub is_local: bool,
 }
 
+impl SimplifiedTrack {
+    /// Returns `true` if the track is available in the given market, or if its markets are unknown.
+    pub fn is_available_in(&self, market: &str) -> bool {
+        is_available_in(
+            self.available_markets.as_deref().unwrap_or_default(),
+            market,
+        )
+    }
+
+    /// The ID of the track that was originally requested, which differs from [`id`](Self::id)
+    /// if the track was relinked.
+    pub fn requested_id(&self) -> &str {
+        self.linked_from.as_ref().map_or(&self.id, |l| &l.id)
+    }
+
+    /// The duration of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct SavedTrack {
     pub added_at: DateTime<Utc>,
     pub track: Track,
 }
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct LinkedFrom {
     pub external_urls: ExternalUrls,
     pub href: String,
@@ -67,3 +126,38 @@ pub struct LinkedFrom {
     pub r#type: String,
     pub uri: String,
 }
+
+/// A track that's stored locally on the user's device.
+///
+/// Local tracks don't exist on Spotify, so most of the fields a [`Track`] has
+/// are missing (including the ID).
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct LocalTrack {
+    pub album: LocalAlbum,
+    pub artists: Vec<LocalArtist>,
+    pub duration_ms: u32,
+    pub name: String,
+    pub r#type: String,
+    pub uri: String,
+    pub is_local: bool,
+}
+
+impl LocalTrack {
+    /// The duration of the track.
+    pub fn duration(&self) -> Duration {
+        Duration::from_millis(self.duration_ms.into())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct LocalAlbum {
+    pub name: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
+pub struct LocalArtist {
+    pub name: Option<String>,
+}
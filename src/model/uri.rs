@@ -0,0 +1,78 @@
+use std::fmt::Display;
+
+/// The *URI* of a context that can be played, i.e. an album, artist, playlist or show.
+///
+/// It can be passed anywhere a context URI is expected, as it converts into a `String`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ContextUri(String);
+
+impl ContextUri {
+    /// The *URI* of the album with the given ID.
+    pub fn album(id: impl AsRef<str>) -> Self {
+        Self(format!("spotify:album:{}", id.as_ref()))
+    }
+
+    /// The *URI* of the artist with the given ID.
+    pub fn artist(id: impl AsRef<str>) -> Self {
+        Self(format!("spotify:artist:{}", id.as_ref()))
+    }
+
+    /// The *URI* of the playlist with the given ID.
+    pub fn playlist(id: impl AsRef<str>) -> Self {
+        Self(format!("spotify:playlist:{}", id.as_ref()))
+    }
+
+    /// The *URI* of the show with the given ID.
+    pub fn show(id: impl AsRef<str>) -> Self {
+        Self(format!("spotify:show:{}", id.as_ref()))
+    }
+}
+
+impl AsRef<str> for ContextUri {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for ContextUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<ContextUri> for String {
+    fn from(value: ContextUri) -> Self {
+        value.0
+    }
+}
+
+/// The *URI* of a track.
+///
+/// It can be passed anywhere track URIs are expected, e.g. when starting playback.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TrackUri(String);
+
+impl TrackUri {
+    /// The *URI* of the track with the given ID.
+    pub fn new(id: impl AsRef<str>) -> Self {
+        Self(format!("spotify:track:{}", id.as_ref()))
+    }
+}
+
+impl AsRef<str> for TrackUri {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for TrackUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<TrackUri> for String {
+    fn from(value: TrackUri) -> Self {
+        value.0
+    }
+}
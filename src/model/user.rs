@@ -5,10 +5,13 @@ use serde::{Deserialize, Serialize};
 use super::{artist::Artist, track::Track, *};
 
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct PrivateUser {
-    pub country: String,
+    /// The user's country, as an ISO 3166-1 alpha-2 country code. This field is only available when the current user has granted access to the user-read-private scope.
+    pub country: Option<String>,
     pub display_name: Option<String>,
-    pub email: String,
+    /// The user's email address. This field is only available when the current user has granted access to the user-read-email scope.
+    pub email: Option<String>,
     /// The user's explicit content settings. This field is only available when the current user has granted access to the user-read-private scope.
     pub explicit_content: Option<ExplicitContent>,
     pub external_urls: ExternalUrls,
@@ -16,13 +19,45 @@ pub struct PrivateUser {
     pub href: String,
     pub id: String,
     pub images: Vec<Image>,
-    /// The user's Spotify subscription level: "premium", "free", etc. (The subscription level "open" can be considered the same as "free".) This field is only available when the current user has granted access to the user-read-private scope.
-    pub product: Option<String>,
+    /// The user's Spotify subscription level. This field is only available when the current user has granted access to the user-read-private scope.
+    pub product: Option<Product>,
     pub r#type: String,
     pub uri: String,
 }
 
+impl PrivateUser {
+    /// The total number of followers.
+    pub fn follower_count(&self) -> u32 {
+        self.followers.total
+    }
+
+    /// The user's country (an ISO 3166-1 alpha-2 country code, e.g. `GB`), if available.
+    pub fn country(&self) -> Option<&str> {
+        self.country.as_deref()
+    }
+
+    /// Returns `true` if the user has a Premium subscription.
+    ///
+    /// This is always `false` if the user-read-private scope wasn't granted.
+    pub fn is_premium(&self) -> bool {
+        self.product == Some(Product::Premium)
+    }
+}
+
+/// A Spotify subscription level.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Product {
+    Premium,
+    Free,
+    /// Can be considered the same as [`Free`](Self::Free).
+    Open,
+    #[serde(other)]
+    Unknown,
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct User {
     pub display_name: Option<String>,
     pub external_urls: ExternalUrls,
@@ -34,8 +69,16 @@ pub struct User {
     pub uri: String,
 }
 
+impl User {
+    /// The total number of followers.
+    pub fn follower_count(&self) -> u32 {
+        self.followers.total
+    }
+}
+
 // Returned by the get/playlist/{id} endpoint; also called "PlaylistUserObject" in the schema
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct ReferenceUser {
     pub external_urls: ExternalUrls,
     pub followers: Option<Followers>,
@@ -46,7 +89,15 @@ pub struct ReferenceUser {
     pub display_name: Option<String>,
 }
 
+impl ReferenceUser {
+    /// The total number of followers, which is `None` if Spotify didn't include it.
+    pub fn follower_count(&self) -> Option<u32> {
+        self.followers.as_ref().map(|f| f.total)
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
+#[non_exhaustive]
 pub struct ExplicitContent {
     pub filter_enabled: bool,
     pub filter_locked: bool,
@@ -70,15 +121,32 @@ impl Display for UserItemType {
     }
 }
 
+/// The time frame over which the user's top items are computed.
+///
+/// Each time range has its own ranking, so an offset only makes sense
+/// within the time range it was used with.
 #[derive(Clone, Debug, Default, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TimeRange {
+    /// Calculated from about a year of data.
     LongTerm,
+    /// Approximately the last 6 months.
     #[default]
     MediumTerm,
+    /// Approximately the last 4 weeks.
     ShortTerm,
 }
 
+impl AsRef<str> for TimeRange {
+    fn as_ref(&self) -> &str {
+        match self {
+            TimeRange::LongTerm => "long_term",
+            TimeRange::MediumTerm => "medium_term",
+            TimeRange::ShortTerm => "short_term",
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(untagged)]
 pub enum UserItem {
@@ -0,0 +1,122 @@
+use std::{fmt::Debug, io};
+
+#[cfg(feature = "token-store")]
+use std::{fs, path::PathBuf};
+
+#[cfg(feature = "token-store")]
+use chrono::{DateTime, Utc};
+#[cfg(feature = "token-store")]
+use serde::{Deserialize, Serialize};
+#[cfg(all(feature = "token-store", feature = "zeroize"))]
+use zeroize::Zeroize;
+
+use crate::auth::Token;
+
+/// Persists the client's token, e.g. so the user doesn't have to log in again every time
+/// the app starts.
+///
+/// Once set with [`Client::set_token_store`](crate::client::Client::set_token_store),
+/// the token is saved every time it's refreshed.
+///
+/// Saving runs on a blocking thread, so it can do blocking I/O. If it fails, the refreshed token
+/// is still used by the client and the error is returned from the request that refreshed it.
+pub trait TokenStore: Debug + Send + Sync {
+    /// Load the saved token, returning `None` if there isn't one.
+    fn load(&self) -> io::Result<Option<Token>>;
+
+    /// Save the token, overwriting the previously saved one.
+    fn save(&self, token: &Token) -> io::Result<()>;
+
+    /// Remove the saved token (if any), e.g. when logging out.
+    fn clear(&self) -> io::Result<()>;
+}
+
+/// A [`TokenStore`] which saves the token to a file, as JSON.
+///
+/// *Note: the token is saved in plain text, so make sure the file isn't readable by others.
+/// With the `zeroize` feature enabled, the serialized token is wiped from memory after
+/// it's written or read.*
+#[cfg(feature = "token-store")]
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+#[cfg(feature = "token-store")]
+impl FileTokenStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+// The token's timestamps aren't serialized by default, but they're needed to know
+// whether a loaded token is still valid.
+#[cfg(feature = "token-store")]
+#[derive(Serialize)]
+struct StoredToken<'a> {
+    #[serde(flatten)]
+    token: &'a Token,
+    expires_at: DateTime<Utc>,
+}
+
+// Deserializing a flattened token would buffer (and leave behind) copies of its secrets,
+// so the expiry is read separately instead.
+#[cfg(feature = "token-store")]
+#[derive(Deserialize)]
+struct StoredExpiry {
+    expires_at: DateTime<Utc>,
+}
+
+#[cfg(feature = "token-store")]
+impl TokenStore for FileTokenStore {
+    fn load(&self) -> io::Result<Option<Token>> {
+        let json = match fs::read(&self.path) {
+            Ok(json) => json,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+
+        let token = serde_json::from_slice::<Token>(&json);
+        let expiry = serde_json::from_slice::<StoredExpiry>(&json);
+
+        // Moving `json` into a block means it doesn't have to be `mut` without the feature.
+        #[cfg(feature = "zeroize")]
+        Zeroize::zeroize(&mut { json });
+
+        let (token, StoredExpiry { expires_at }) = (token?, expiry?);
+        let expires_in = chrono::Duration::seconds(i64::try_from(token.expires_in).unwrap_or(0));
+
+        Ok(Some(Token {
+            created_at: expires_at - expires_in,
+            expires_at,
+            ..token
+        }))
+    }
+
+    fn save(&self, token: &Token) -> io::Result<()> {
+        // Reserving enough space up front means the buffer isn't reallocated (which would leave
+        // copies of the token behind) while serializing.
+        let mut json = Vec::with_capacity(4096);
+        serde_json::to_writer(
+            &mut json,
+            &StoredToken {
+                token,
+                expires_at: token.expires_at,
+            },
+        )?;
+
+        let res = fs::write(&self.path, &json);
+
+        #[cfg(feature = "zeroize")]
+        Zeroize::zeroize(&mut { json });
+
+        res
+    }
+
+    fn clear(&self) -> io::Result<()> {
+        match fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use std::fmt::Debug;
+
+#[cfg(not(target_arch = "wasm32"))]
+use futures_util::future::BoxFuture;
+#[cfg(target_arch = "wasm32")]
+use futures_util::future::LocalBoxFuture;
+use reqwest::{Request, Response};
+
+use crate::error::Result;
+
+/// The future returned by [`HttpTransport::send`].
+#[cfg(not(target_arch = "wasm32"))]
+pub type TransportFuture<'a> = BoxFuture<'a, Result<Response>>;
+
+/// The future returned by [`HttpTransport::send`].
+#[cfg(target_arch = "wasm32")]
+pub type TransportFuture<'a> = LocalBoxFuture<'a, Result<Response>>;
+
+/// The HTTP backend used to send the API requests.
+///
+/// By default, requests are sent using [`reqwest::Client`], but a custom transport can be set
+/// with [`Client::set_transport`](crate::client::Client::set_transport),
+/// e.g. to use another HTTP stack or to mock responses in tests.
+///
+/// Requests and responses are still represented with [`reqwest`]'s types, which can be converted
+/// to and from the [`http`](https://docs.rs/http) crate's types.
+///
+/// With the `middleware` feature enabled, a `reqwest_middleware::ClientWithMiddleware` can be used
+/// as the transport, e.g. to retry or trace requests with existing middleware.
+///
+/// *Note: the requests made during authentication (e.g. refreshing the token) don't go through the transport.*
+pub trait HttpTransport: Debug + Send + Sync {
+    /// Send the request and return the response.
+    fn send(&self, request: Request) -> TransportFuture<'_>;
+}
+
+impl HttpTransport for reqwest::Client {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(self.execute(request).await?) })
+    }
+}
+
+#[cfg(feature = "middleware")]
+impl HttpTransport for reqwest_middleware::ClientWithMiddleware {
+    fn send(&self, request: Request) -> TransportFuture<'_> {
+        Box::pin(async move { Ok(self.execute(request).await?) })
+    }
+}
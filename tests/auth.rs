@@ -0,0 +1,88 @@
+use spotify_rs::{
+    auth::{parse_callback, CallbackError, Scope, Token},
+    Url,
+};
+
+#[test]
+fn scopes_match_documented_strings() {
+    let scopes = [
+        (Scope::UgcImageUpload, "ugc-image-upload"),
+        (Scope::UserReadPlaybackState, "user-read-playback-state"),
+        (Scope::UserModifyPlaybackState, "user-modify-playback-state"),
+        (
+            Scope::UserReadCurrentlyPlaying,
+            "user-read-currently-playing",
+        ),
+        (Scope::AppRemoteControl, "app-remote-control"),
+        (Scope::Streaming, "streaming"),
+        (Scope::PlaylistReadPrivate, "playlist-read-private"),
+        (
+            Scope::PlaylistReadCollaborative,
+            "playlist-read-collaborative",
+        ),
+        (Scope::PlaylistModifyPrivate, "playlist-modify-private"),
+        (Scope::PlaylistModifyPublic, "playlist-modify-public"),
+        (Scope::UserFollowModify, "user-follow-modify"),
+        (Scope::UserFollowRead, "user-follow-read"),
+        (
+            Scope::UserReadPlaybackPosition,
+            "user-read-playback-position",
+        ),
+        (Scope::UserTopRead, "user-top-read"),
+        (Scope::UserReadRecentlyPlayed, "user-read-recently-played"),
+        (Scope::UserLibraryModify, "user-library-modify"),
+        (Scope::UserLibraryRead, "user-library-read"),
+        (Scope::UserReadEmail, "user-read-email"),
+        (Scope::UserReadPrivate, "user-read-private"),
+        (Scope::UserSoaLink, "user-soa-link"),
+        (Scope::UserSoaUnlink, "user-soa-unlink"),
+        (Scope::SoaManageEntitlements, "soa-manage-entitlements"),
+        (Scope::SoaManagePartner, "soa-manage-partner"),
+        (Scope::SoaCreatePartner, "soa-create-partner"),
+    ];
+
+    for (scope, expected) in scopes {
+        assert_eq!(scope.as_ref(), expected);
+        assert_eq!(String::from(scope), expected);
+    }
+}
+
+#[test]
+fn callback_url() {
+    let url = Url::parse("http://localhost:8888/callback?code=abc&state=xyz").unwrap();
+    assert_eq!(
+        parse_callback(&url),
+        Ok(("abc".to_owned(), "xyz".to_owned()))
+    );
+
+    let url = Url::parse("http://localhost:8888/callback?error=access_denied&state=xyz").unwrap();
+    assert_eq!(
+        parse_callback(&url),
+        Err(CallbackError::AuthorizationDenied {
+            reason: "access_denied".to_owned()
+        })
+    );
+
+    let err = spotify_rs::Error::from(parse_callback(&url).unwrap_err());
+    assert!(
+        matches!(err, spotify_rs::Error::AuthorizationDenied { reason } if reason == "access_denied")
+    );
+
+    let url = Url::parse("http://localhost:8888/callback?state=xyz").unwrap();
+    assert_eq!(
+        parse_callback(&url),
+        Err(CallbackError::MissingParameter("code"))
+    );
+}
+
+#[test]
+fn token_from_parts() {
+    let token = Token::new("access", Some("refresh".to_owned()), 3600);
+
+    assert!(!token.is_expired());
+    assert!(token.is_refreshable());
+    assert_eq!(
+        token.expires_at - token.created_at,
+        chrono::Duration::seconds(3600)
+    );
+}
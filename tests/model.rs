@@ -0,0 +1,357 @@
+use std::time::Duration;
+
+use spotify_rs::{
+    endpoint::player::RepeatMode,
+    model::{
+        album::Album,
+        artist::Artist,
+        audio::AudioAnalysis,
+        audiobook::{Audiobook, Chapter},
+        category::Category,
+        player::{ContextType, Device, DeviceType, PlayHistory, PlaybackState, Queue},
+        playlist::{Playlist, SimplifiedPlaylist},
+        search::{Item, SearchResults},
+        show::{Episode, Show},
+        track::{SimplifiedTrack, Track},
+        user::{PrivateUser, Product, UserItem},
+        CursorPage, HasExternalUrls, Page, PlayableItem, RestrictionReason, SpotifyObject,
+    },
+};
+
+#[test]
+fn queue_with_track_and_episode() {
+    let queue: Queue = serde_json::from_str(include_str!("fixtures/queue.json")).unwrap();
+
+    let Some(PlayableItem::Track(track)) = &queue.currently_playing else {
+        panic!("expected the currently playing item to be a track");
+    };
+    assert_eq!(track.name, "Cut To The Feeling");
+
+    // The malformed entry is skipped.
+    assert_eq!(queue.queue.len(), 1);
+
+    let PlayableItem::Episode(episode) = &queue.queue[0] else {
+        panic!("expected the queued item to be an episode");
+    };
+    assert_eq!(episode.name, "Starting Your Own Podcast");
+}
+
+#[test]
+fn search_item_round_trip() {
+    for item in Item::all() {
+        assert_eq!(item.to_string().parse::<Item>().unwrap(), *item);
+    }
+
+    assert_eq!("Audiobook".parse::<Item>().unwrap(), Item::Audiobook);
+    assert!("podcast".parse::<Item>().is_err());
+}
+
+#[test]
+fn categories_page() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/categories.json")).unwrap();
+    let page: Page<Category> = serde_json::from_value(json["categories"].take()).unwrap();
+
+    assert_eq!(page.total, 52);
+    assert_eq!(page.items.len(), 2);
+
+    let hip_hop = &page.items[1];
+    assert_eq!(hip_hop.name, "Hip-Hop");
+    assert_eq!(hip_hop.largest_icon().unwrap().width, Some(640));
+    assert_eq!(hip_hop.icon_at_least(100).unwrap().width, Some(274));
+    assert_eq!(hip_hop.icon_at_least(1000).unwrap().width, Some(640));
+}
+
+#[test]
+fn playlist_with_track_and_episode() {
+    let playlist: Playlist = serde_json::from_str(include_str!("fixtures/playlist.json")).unwrap();
+
+    assert_eq!(playlist.tracks.items.len(), 2);
+    assert!(matches!(
+        playlist.tracks.items[0].track,
+        Some(PlayableItem::Track(_))
+    ));
+    assert!(matches!(
+        playlist.tracks.items[1].track,
+        Some(PlayableItem::Episode(_))
+    ));
+}
+
+#[test]
+fn playlist_owner_without_display_name() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/playlist.json")).unwrap();
+    json["owner"]["display_name"] = serde_json::Value::Null;
+
+    let playlist: Playlist = serde_json::from_value(json).unwrap();
+
+    assert!(playlist.owner.display_name.is_none());
+    assert!(playlist.is_owned_by("listener"));
+    assert!(!playlist.is_owned_by("someone_else"));
+}
+
+#[test]
+fn relinked_album_tracks() {
+    let page: Page<SimplifiedTrack> =
+        serde_json::from_str(include_str!("fixtures/album_tracks.json")).unwrap();
+
+    let original = &page.items[0];
+    assert!(original.linked_from.is_none());
+    assert_eq!(original.requested_id(), original.id);
+
+    let relinked = &page.items[1];
+    assert_eq!(relinked.is_playable, Some(true));
+    assert_eq!(relinked.id, "6kLCHFM39wkFjOuyPGLGeQ");
+    assert_eq!(relinked.requested_id(), "2HRgqmZQC0MC7GeNuDIXHN");
+}
+
+#[test]
+fn top_items_pages() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/top_items.json")).unwrap();
+
+    let tracks: Page<UserItem> = serde_json::from_value(json["tracks"].take()).unwrap();
+    assert_eq!((tracks.offset, tracks.limit, tracks.total), (1, 1, 50));
+    assert!(tracks.next.is_some());
+    let UserItem::Track(track) = &tracks.items[0] else {
+        panic!("expected a track");
+    };
+    assert_eq!(track.name, "Cut To The Feeling");
+
+    let artists: Page<UserItem> = serde_json::from_value(json["artists"].take()).unwrap();
+    assert_eq!((artists.offset, artists.limit, artists.total), (1, 1, 50));
+    let UserItem::Artist(artist) = &artists.items[0] else {
+        panic!("expected an artist");
+    };
+    assert_eq!(artist.follower_count(), 10500000);
+}
+
+#[test]
+fn album() {
+    let album: Album = serde_json::from_str(include_str!("fixtures/album.json")).unwrap();
+
+    assert_eq!(album.name, "Global Warming");
+    assert_eq!(album.upc(), Some("886443671584"));
+    assert_eq!(album.copyright_text(), Some("(C) 2012 RCA Records"));
+    assert_eq!(album.tracks.total, 10);
+    assert_eq!(album.tracks.items[0].name, "Cut To The Feeling");
+}
+
+#[test]
+fn artist() {
+    let artist: Artist = serde_json::from_str(include_str!("fixtures/artist.json")).unwrap();
+
+    assert_eq!(artist.name, "Pitbull");
+    assert_eq!(artist.genres.len(), 3);
+    assert_eq!(
+        artist.spotify_url(),
+        "https://open.spotify.com/artist/0TnOYISbd1XYRBk9myaseg"
+    );
+}
+
+#[test]
+fn track_requested_with_market() {
+    let track: Track = serde_json::from_str(include_str!("fixtures/track.json")).unwrap();
+
+    // The markets are omitted when a market is specified in the request.
+    assert!(track.available_markets.is_none());
+    assert!(track.album.available_markets.is_empty());
+    assert_eq!(track.is_playable, Some(false));
+    assert!(matches!(
+        track.restrictions.map(|r| r.reason),
+        Some(RestrictionReason::Market)
+    ));
+}
+
+#[test]
+fn show() {
+    let show: Show = serde_json::from_str(include_str!("fixtures/show.json")).unwrap();
+
+    assert_eq!(show.episode_count(), 100);
+    assert_eq!(show.episodes.items[0].name, "Starting Your Own Podcast");
+}
+
+#[test]
+fn episode() {
+    let episode: Episode = serde_json::from_str(include_str!("fixtures/episode.json")).unwrap();
+
+    assert_eq!(episode.show.total_episodes, 100);
+    assert!(episode.is_available_in("gb"));
+    assert!(!episode.is_available_in("SE"));
+    assert_eq!(episode.language_codes(), ["en", "es"]);
+    assert_eq!(episode.primary_language().as_deref(), Some("en"));
+    assert_eq!(episode.show.primary_language().as_deref(), Some("en"));
+    assert_eq!(
+        episode.resume_point.unwrap().resume_position(),
+        Duration::from_secs(1234)
+    );
+}
+
+#[test]
+fn audio_analysis_lookups() {
+    let analysis: AudioAnalysis =
+        serde_json::from_str(include_str!("fixtures/audio_analysis.json")).unwrap();
+
+    assert_eq!(analysis.section_at(0.0).unwrap().key, 7);
+    assert_eq!(analysis.section_at(3.0).unwrap().key, 9);
+    assert!(analysis.section_at(8.0).is_none());
+    assert!(analysis.section_at(-1.0).is_none());
+
+    assert_eq!(analysis.segment_at(0.8).unwrap().start, 0.75);
+    // There's a gap between the third and fourth segments.
+    assert!(analysis.segment_at(1.1).is_none());
+
+    let beats: Vec<f32> = analysis.beats_between(1.0, 2.5).map(|b| b.start).collect();
+    assert_eq!(beats, [1.0, 1.5, 2.0]);
+    assert_eq!(analysis.bars_between(2.0, 6.0).count(), 2);
+    assert_eq!(analysis.bars_between(5.0, 1.0).count(), 0);
+}
+
+#[test]
+fn audiobook() {
+    let audiobook: Audiobook =
+        serde_json::from_str(include_str!("fixtures/audiobook.json")).unwrap();
+
+    assert_eq!(audiobook.chapter_count(), 2);
+    assert_eq!(audiobook.narrators.len(), 2);
+    assert_eq!(audiobook.chapters.items[0].chapter_number, 0);
+}
+
+#[test]
+fn chapter() {
+    let chapter: Chapter = serde_json::from_str(include_str!("fixtures/chapter.json")).unwrap();
+
+    assert_eq!(chapter.audiobook.name, "Dune: Book One");
+    assert_eq!(chapter.audiobook.total_chapters, Some(2));
+}
+
+#[test]
+fn current_user() {
+    let user: PrivateUser =
+        serde_json::from_str(include_str!("fixtures/current_user.json")).unwrap();
+
+    assert_eq!(user.product, Some(Product::Premium));
+    assert_eq!(user.follower_count(), 12);
+}
+
+#[test]
+fn playback_state() {
+    let state: PlaybackState =
+        serde_json::from_str(include_str!("fixtures/playback_state.json")).unwrap();
+
+    assert!(state.is_playing);
+    assert_eq!(state.device.as_ref().unwrap().volume_percent, Some(59));
+    assert_eq!(state.device.as_ref().unwrap().r#type, DeviceType::Speaker);
+    assert_eq!(state.progress(), Some(Duration::from_millis(103979)));
+    assert!(matches!(state.item(), Some(PlayableItem::Track(_))));
+    assert_eq!(state.repeat_mode(), Some(RepeatMode::Off));
+    assert_eq!(state.shuffle_state, Some(false));
+}
+
+#[test]
+fn devices() {
+    let json: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/devices.json")).unwrap();
+    let devices: Vec<Device> = serde_json::from_value(json["devices"].clone()).unwrap();
+
+    assert_eq!(devices[0].r#type, DeviceType::CastAudio);
+    assert!(!devices[0].supports_volume);
+    assert_eq!(devices[0].volume_percent, None);
+
+    assert_eq!(devices[1].r#type, DeviceType::Computer);
+    assert!(devices[1].supports_volume);
+
+    assert_eq!(devices[2].r#type, DeviceType::Unknown);
+    assert!(!devices[2].supports_volume);
+}
+
+#[test]
+fn playlists_with_null_images() {
+    let page: Page<SimplifiedPlaylist> =
+        serde_json::from_str(include_str!("fixtures/current_user_playlists.json")).unwrap();
+
+    assert_eq!(page.items.len(), 2);
+    assert!(page.items[0].images.is_empty());
+    assert!(page.items[1].images.is_empty());
+    assert_eq!(page.items[1].tracks.as_ref().unwrap().total, 0);
+    assert_eq!(page.items[0].track_count(), 2);
+}
+
+#[test]
+fn simplified_playlist_with_full_tracks_page() {
+    let mut json: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/playlist.json")).unwrap();
+    json["tracks"]["total"] = 7.into();
+    let playlist: SimplifiedPlaylist = serde_json::from_value(json).unwrap();
+
+    assert_eq!(playlist.track_count(), 7);
+}
+
+#[test]
+fn search_results() {
+    let results: SearchResults =
+        serde_json::from_str(include_str!("fixtures/search.json")).unwrap();
+
+    assert_eq!(results.total_for(Item::Track), Some(900));
+    assert_eq!(results.total_for(Item::Album), None);
+    assert!(!results.is_empty());
+    assert_eq!(results.artists.unwrap().items[0].name, "Pitbull");
+}
+
+#[test]
+fn empty_search_results() {
+    let results: SearchResults = serde_json::from_value(serde_json::json!({
+        "albums": {
+            "href": "https://api.spotify.com/v1/search?query=zzzz&type=album&offset=0&limit=20",
+            "limit": 20,
+            "next": null,
+            "offset": 0,
+            "previous": null,
+            "total": 0,
+            "items": []
+        }
+    }))
+    .unwrap();
+
+    // Requested but empty, as opposed to not requested.
+    assert_eq!(results.total_for(Item::Album), Some(0));
+    assert_eq!(results.total_for(Item::Track), None);
+    assert!(results.is_empty());
+}
+
+#[test]
+fn recently_played() {
+    let page: CursorPage<PlayHistory> =
+        serde_json::from_str(include_str!("fixtures/recently_played.json")).unwrap();
+
+    assert_eq!(page.next_before(), Some("1704448800000"));
+
+    let latest = &page.items[0];
+    assert_eq!(latest.track().name, "Cut To The Feeling");
+    assert_eq!(latest.played_at.timestamp_millis(), 1704452400000);
+    assert_eq!(
+        latest.context.as_ref().unwrap().r#type,
+        ContextType::Playlist
+    );
+
+    assert!(page.items[1].context.is_none());
+    assert!(page.items[1].played_at < latest.played_at);
+}
+
+#[test]
+fn spotify_objects() {
+    fn summary(object: &impl SpotifyObject) -> String {
+        format!("{} {} {}", object.id(), object.uri(), object.spotify_url())
+    }
+
+    let track: Track = serde_json::from_str(include_str!("fixtures/track.json")).unwrap();
+    assert_eq!(
+        summary(&track),
+        "11dFghVXANMlKmJXsNCbNl spotify:track:11dFghVXANMlKmJXsNCbNl \
+         https://open.spotify.com/track/11dFghVXANMlKmJXsNCbNl"
+    );
+    assert_eq!(
+        track.album.href(),
+        "https://api.spotify.com/v1/albums/4aawyAB9vmqN3uQ7FjRGTy"
+    );
+}
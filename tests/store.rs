@@ -0,0 +1,27 @@
+#![cfg(feature = "token-store")]
+
+use spotify_rs::{
+    auth::Token,
+    store::{FileTokenStore, TokenStore},
+};
+
+#[test]
+fn file_store_round_trip() {
+    let path = std::env::temp_dir().join(format!("spotify-rs-token-{}.json", std::process::id()));
+    let store = FileTokenStore::new(&path);
+    let token = Token::new("access", Some("refresh".to_owned()), 3600);
+
+    store.save(&token).unwrap();
+    let loaded = store.load().unwrap().unwrap();
+
+    assert_eq!(loaded.access_token.secret(), "access");
+    assert_eq!(loaded.refresh_token.unwrap().secret(), "refresh");
+    assert_eq!(loaded.expires_in, 3600);
+    assert_eq!(loaded.expires_at, token.expires_at);
+    assert_eq!(loaded.created_at, token.created_at);
+
+    store.clear().unwrap();
+    assert!(store.load().unwrap().is_none());
+    // Clearing a store without a saved token isn't an error.
+    store.clear().unwrap();
+}